@@ -0,0 +1,34 @@
+//! A minimal timing harness shared by the benchmarks, which run with
+//! `cargo bench` on stable Rust without external dependencies.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Runs `f` once to warm up, then `iters` times, and prints the median time per run.
+///
+/// The median is less affected by other load on the machine than the mean.
+pub fn bench<T>(name: &str, iters: u32, mut f: impl FnMut() -> T) -> Duration {
+    black_box(f());
+    let mut times: Vec<Duration> = (0..iters)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    times.sort_unstable();
+    let median = times[times.len() / 2];
+    println!("{:<40} {:>12.3?}", name, median);
+    median
+}
+
+/// Returns a deterministic sequence of pseudo-random numbers.
+pub fn numbers() -> impl Iterator<Item = u64> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    std::iter::repeat_with(move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    })
+}
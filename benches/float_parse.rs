@@ -0,0 +1,43 @@
+//! Compares `FastInput::next_float` against reading the same tokens with
+//! `next_token`, which parses them with `str::parse`.
+
+mod common;
+
+use common::{bench, numbers};
+use fast_input::FastInput;
+
+const COUNT: usize = 1_000_000;
+
+/// Whitespace separated decimals between -180 and 180 with `decimals`
+/// digits after the point, such as the coordinate `-123.456789`.
+fn data(decimals: usize) -> Vec<u8> {
+    let mut data = String::new();
+    for (i, n) in numbers().take(COUNT).enumerate() {
+        let value = (n % 360_000_000) as f64 / 1e6 - 180.0;
+        data.push_str(&format!("{:.*}", decimals, value));
+        data.push(if i % 2 == 0 { ' ' } else { '\n' });
+    }
+    data.into_bytes()
+}
+
+fn main() {
+    for &decimals in &[1, 6] {
+        let input = FastInput::from_bytes(data(decimals));
+        bench(&format!("{} decimals, f64: next_float", decimals), 10, || {
+            input.reset();
+            (0..COUNT).map(|_| input.next_float::<f64>()).sum::<f64>()
+        });
+        bench(&format!("{} decimals, f64: str::parse", decimals), 10, || {
+            input.reset();
+            (0..COUNT).map(|_| input.next_token::<f64>()).sum::<f64>()
+        });
+        bench(&format!("{} decimals, f32: next_float", decimals), 10, || {
+            input.reset();
+            (0..COUNT).map(|_| input.next_float::<f32>()).sum::<f32>()
+        });
+        bench(&format!("{} decimals, f32: str::parse", decimals), 10, || {
+            input.reset();
+            (0..COUNT).map(|_| input.next_token::<f32>()).sum::<f32>()
+        });
+    }
+}
@@ -0,0 +1,65 @@
+//! Compares `FastInput::lines`, which finds newlines with a word-at-a-time
+//! scan, against splitting the same data with a byte-by-byte loop.
+
+mod common;
+
+use common::{bench, numbers};
+use fast_input::FastInput;
+use std::str::from_utf8_unchecked;
+
+const SIZE: usize = 10 << 20;
+
+/// Lines of numbers, each about `line_len` bytes long.
+fn data(line_len: usize) -> Vec<u8> {
+    let mut data = Vec::with_capacity(SIZE + line_len);
+    let mut line = String::new();
+    for n in numbers() {
+        if data.len() >= SIZE {
+            break;
+        }
+        line.push_str(&(n % 1_000_000).to_string());
+        if line.len() >= line_len {
+            data.extend_from_slice(line.as_bytes());
+            data.push(b'\n');
+            line.clear();
+        } else {
+            line.push(' ');
+        }
+    }
+    data
+}
+
+/// Splits `data` into lines like `FastInput::lines`, scanning for newlines one byte at a time.
+fn byte_loop_lines(data: &[u8]) -> impl Iterator<Item = &str> {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        if pos == data.len() {
+            return None;
+        }
+        let rest = &data[pos..];
+        let (line, next) = match rest.iter().position(|&b| b == b'\n') {
+            Some(i) => (&rest[..i], pos + i + 1),
+            None => (rest, data.len()),
+        };
+        pos = next;
+        let line = match line.last() {
+            Some(b'\r') => &line[..line.len() - 1],
+            _ => line,
+        };
+        Some(unsafe { from_utf8_unchecked(line) })
+    })
+}
+
+fn main() {
+    for &(name, line_len) in &[("short lines", 16), ("long lines", 1000)] {
+        let data = data(line_len);
+        let input = FastInput::from_bytes(data.clone());
+        bench(&format!("{}: FastInput::lines", name), 20, || {
+            input.reset();
+            input.lines().map(str::len).sum::<usize>()
+        });
+        bench(&format!("{}: byte loop", name), 20, || {
+            byte_loop_lines(&data).map(str::len).sum::<usize>()
+        });
+    }
+}
@@ -0,0 +1,66 @@
+//! Compares `FastInput::next_as_iter`, which maps the line split through a
+//! closure, against a concrete `ParseIter` struct calling `FParse::fparse`
+//! from an inlined `next`.
+
+mod common;
+
+use common::{bench, numbers};
+use fast_input::{FParse, FastInput};
+use std::marker::PhantomData;
+
+const LINES: usize = 200_000;
+const PER_LINE: usize = 20;
+
+struct ParseIter<'a, I, T> {
+    split: I,
+    _parsed: PhantomData<&'a T>,
+}
+
+impl<'a, I: Iterator<Item = &'a str>, T: FParse<'a>> Iterator for ParseIter<'a, I, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.split.next().map(T::fparse)
+    }
+}
+
+/// Lines of space separated integers below 10^9.
+fn data() -> Vec<u8> {
+    let mut data = String::new();
+    let mut numbers = numbers();
+    for _ in 0..LINES {
+        for i in 0..PER_LINE {
+            if i > 0 {
+                data.push(' ');
+            }
+            data.push_str(&(numbers.next().unwrap() % 1_000_000_000).to_string());
+        }
+        data.push('\n');
+    }
+    data.into_bytes()
+}
+
+fn main() {
+    let input = FastInput::from_bytes(data());
+    bench("next_as_iter (closure)", 20, || {
+        input.reset();
+        let mut sum = 0u64;
+        while input.has_next_line() {
+            sum += input.next_as_iter::<u64>().sum::<u64>();
+        }
+        sum
+    });
+    bench("ParseIter (struct)", 20, || {
+        input.reset();
+        let mut sum = 0u64;
+        while input.has_next_line() {
+            let parsed: ParseIter<_, u64> = ParseIter {
+                split: input.next_split(),
+                _parsed: PhantomData,
+            };
+            sum += parsed.sum::<u64>();
+        }
+        sum
+    });
+}
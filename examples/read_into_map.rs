@@ -5,7 +5,9 @@ fn main() {
     println!("Enter String number tuples (hello 2)");
     let inp = FastInput::new();
     let mut people = HashMap::new();
-    while inp.has_next_line() {
+    // `next` reads a whole line as a tuple, `has_next_token` (unlike
+    // `has_next_line`) is false if only blank lines remain.
+    while inp.has_next_token() {
         let (name, age): (Str, u16) = inp.next();
         *people.entry(*name).or_default() = age;
     }
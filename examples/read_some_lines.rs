@@ -5,6 +5,7 @@ fn main() {
     solve();
 }
 
+#[allow(clippy::extra_unused_lifetimes)]
 fn solve<'a>() {
     print!("Enter some text, end with EOF (Ctrl + D): ");
     std::io::stdout().flush().expect("Failed to flush stdout..");
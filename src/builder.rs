@@ -0,0 +1,117 @@
+use std::io::prelude::*;
+use std::io::stdin;
+
+use crate::{FastInput, BUFFER_SIZE};
+
+/// Configures and creates a [`FastInput`], see [`FastInput::builder`].
+///
+/// All settings default to the behavior of [`FastInput::new`].
+///
+/// # Examples
+///
+/// Reading tab separated values, keeping `\r` at the end of lines:
+/// ```
+/// use fast_input::FastInputBuilder;
+///
+/// let input = FastInputBuilder::new()
+///     .delimiter('\t')
+///     .trim(false)
+///     .crlf(false)
+///     .build_from_reader("a\tb\t\r\n".as_bytes());
+/// let fields: Vec<_> = input.next_split().collect();
+/// assert_eq!(vec!["a", "b", "\r"], fields);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FastInputBuilder {
+    delimiter: char,
+    trim: bool,
+    crlf: bool,
+    line_terminator: u8,
+    null_token: String,
+    group_separator: char,
+    capacity: usize,
+}
+
+impl FastInputBuilder {
+    /// Creates a builder with the default settings.
+    pub fn new() -> Self {
+        FastInputBuilder {
+            delimiter: ' ',
+            trim: true,
+            crlf: true,
+            line_terminator: b'\n',
+            null_token: String::new(),
+            group_separator: ',',
+            capacity: BUFFER_SIZE,
+        }
+    }
+
+    /// Sets the delimiter, see [`FastInput::with_delimiter`].
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets whether lines are trimmed before being split, see [`FastInput::with_trim`].
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Sets whether a `\r` before the `\n` ending a line is removed, the default is `true`.
+    pub fn crlf(mut self, crlf: bool) -> Self {
+        self.crlf = crlf;
+        self
+    }
+
+    /// Sets the byte ending a line, see [`FastInput::with_line_terminator`].
+    ///
+    /// # Panics
+    /// If `terminator` is not ASCII.
+    pub fn line_terminator(mut self, terminator: u8) -> Self {
+        assert!(terminator.is_ascii(), "line terminator {:?} is not ASCII", terminator);
+        self.line_terminator = terminator;
+        self
+    }
+
+    /// Sets the token representing a missing value, see [`FastInput::with_null_token`].
+    pub fn null_token<S: Into<String>>(mut self, null_token: S) -> Self {
+        self.null_token = null_token.into();
+        self
+    }
+
+    /// Sets the group separator, see [`FastInput::with_group_separator`].
+    pub fn group_separator(mut self, separator: char) -> Self {
+        self.group_separator = separator;
+        self
+    }
+
+    /// Sets the initial buffer size, see [`FastInput::with_buffer_size`].
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Creates a FastInput reading all of standard input.
+    pub fn build(self) -> FastInput {
+        self.build_from_reader(stdin().lock())
+    }
+
+    /// Creates a FastInput reading all of `input`.
+    pub fn build_from_reader<T: Read>(self, input: T) -> FastInput {
+        let mut fast_input = FastInput::with_reader_capacity(input, self.capacity);
+        fast_input.delimiter = self.delimiter;
+        fast_input.trim = self.trim;
+        fast_input.crlf = self.crlf;
+        fast_input.line_terminator = self.line_terminator;
+        fast_input.null_token = self.null_token;
+        fast_input.group_separator = self.group_separator;
+        fast_input
+    }
+}
+
+impl Default for FastInputBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,24 +1,51 @@
-use std::cell::Cell;
+use std::any::type_name;
+use std::borrow::{Borrow, Cow};
+use std::cell::{Cell, RefCell, UnsafeCell};
+use std::collections::{HashMap, HashSet};
+use std::convert::TryInto;
 use std::fmt::Display;
+use std::hash::Hash;
 use std::io::prelude::*;
-use std::io::stdin;
+use std::iter::{FromIterator, FusedIterator};
+use std::io::ErrorKind;
+use std::io::{stdin, BufReader};
+use std::num::ParseIntError;
 use std::ops::Deref;
-use std::str::{from_utf8_unchecked, FromStr};
+use std::str::{from_utf8_unchecked, FromStr, Utf8Error};
 
+mod builder;
+#[cfg(all(feature = "mmap", unix, target_pointer_width = "64"))]
+mod mmap;
+mod output;
+mod sync;
 #[cfg(test)]
 mod tests;
 
+pub use builder::FastInputBuilder;
+pub use output::FastOutput;
+pub use sync::SyncFastInput;
+
 /// Simplifies reading and parsing of known input in a speedy fashion.
 ///
 /// Reads all data on standard in into a byte buffer. Provides
 /// methods to simplify reading and parsing of lines, specifically
 /// aimed to aid in competetive programming where the input is
 /// known and correct. Most functions panic if the input is not correct
-/// and on the specified format. **Note: FastInput assumes *nix line-endings (`\n`)**.
+/// and on the specified format. Lines may end with either `\n` or `\r\n`,
+/// the trailing `\r` is never part of a returned line. A leading UTF-8 byte
+/// order mark (BOM) is skipped.
 ///
 /// FastInput uses interior mutability to allow for zero-copy reading and referencing
 /// of string input.
 ///
+/// **Note: the input must be valid UTF-8.** For speed, string slices are created
+/// from the input without validation, so reading invalid UTF-8 through methods
+/// returning `&str` is undefined behavior. If the input may contain invalid UTF-8,
+/// use [`with_reader_checked`](FastInput::with_reader_checked) (or
+/// [`new_checked`](FastInput::new_checked)), which validates the input once
+/// upon creation, or the byte based methods such as
+/// [`next_line_bytes`](FastInput::next_line_bytes).
+///
 /// # Examples
 ///
 /// Creating a new `FastInput` and reading two lines:
@@ -61,11 +88,36 @@ mod tests;
 /// assert_eq!(map["Sven"], 12);
 /// ```
 pub struct FastInput {
-    data: Vec<u8>,
+    data: UnsafeCell<Vec<u8>>,
+    /// Start of the input in `data`, after a skipped byte order mark.
+    start: usize,
     pos: Cell<usize>,
+    delimiter: char,
+    trim: bool,
+    /// Whether a `\r` before a line's `\n` is removed.
+    crlf: bool,
+    /// Byte ending a line, `\n` by default.
+    line_terminator: u8,
+    /// Token read as `None` by the optional parsing methods.
+    null_token: String,
+    /// Separator removed from numbers by `next_grouped`.
+    group_separator: char,
+    /// Cached result of `max_line_len`.
+    max_line_len: Cell<Option<usize>>,
+    /// Start of the line last read by `next_line_bytes`, used by `unread_line`.
+    line_start: Cell<Option<usize>>,
+    /// Source of more data in streaming mode, `None` once exhausted.
+    source: RefCell<Option<Box<dyn BufRead + Send>>>,
+    /// Buffers replaced while streaming, kept alive as returned slices may point into them.
+    retired: RefCell<Vec<Vec<u8>>>,
+    /// Memory mapped input, used instead of `data` when set.
+    #[cfg(all(feature = "mmap", unix, target_pointer_width = "64"))]
+    mmap: Option<mmap::Mmap>,
 }
 
 const BUFFER_SIZE: usize = 8196;
+/// UTF-8 byte order mark, skipped at the start of the input.
+const BOM: &[u8] = b"\xEF\xBB\xBF";
 
 #[allow(dead_code)]
 impl FastInput {
@@ -76,20 +128,14 @@ impl FastInput {
     /// terminal you can send EOF using `CTRL + D`. The initial buffer size
     /// is 8196 bytes.
     pub fn new() -> Self {
-        FastInput {
-            data: FastInput::read_to_end(stdin().lock(), BUFFER_SIZE),
-            pos: Cell::new(0),
-        }
+        FastInput::from_data(FastInput::read_to_end(stdin().lock(), BUFFER_SIZE))
     }
 
     /// Creates a new FastInput with a specified buffer size.
     ///
     /// For more information, see [`new`].
     pub fn with_buffer_size(buffer_size: usize) -> Self {
-        FastInput {
-            data: FastInput::read_to_end(stdin().lock(), buffer_size),
-            pos: Cell::new(0),
-        }
+        FastInput::from_data(FastInput::read_to_end(stdin().lock(), buffer_size))
     }
 
     /// Creates a new FastInput with a given input that implements
@@ -113,284 +159,3524 @@ impl FastInput {
     /// assert_eq!((3, 4), (three, four));
     /// assert_eq!(false, input.has_next_line());
     /// ```
+    /// # Panics
+    /// If reading fails, see [`try_with_reader`] for a non-panicking version.
+    ///
     /// For more information, see [`new`].
     pub fn with_reader<T: Read>(input: T) -> Self {
-        FastInput {
-            data: FastInput::read_to_end(input, BUFFER_SIZE),
-            pos: Cell::new(0),
-        }
+        FastInput::from_data(FastInput::read_to_end(input, BUFFER_SIZE))
     }
 
-    /// Reads the next line and returns it.
+    /// Creates a new FastInput with a given input that implements `Read`, and a
+    /// specified initial buffer size.
     ///
-    /// # Panics
+    /// Useful for large inputs of known size, to avoid repeated reallocations
+    /// while reading.
     ///
-    /// The function panics if there is no more data in the buffer.
-    /// If you are unsure if there is a next line, see [`has_next_line`].
-    pub fn next_line(&self) -> &str {
-        if let Some(nline) = self.next_newline() {
-            unsafe {
-                let pos = self.pos.get();
-                let s = from_utf8_unchecked(&self.data[pos..nline]);
-                self.pos.set(nline + 1);
-                s
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader_capacity("1 2\n3 4".as_bytes(), 1 << 20);
+    /// assert_eq!("1 2", input.next_line());
+    /// ```
+    /// For more information, see [`with_reader`].
+    pub fn with_reader_capacity<T: Read>(input: T, capacity: usize) -> Self {
+        FastInput::from_data(FastInput::read_to_end(input, capacity))
+    }
+
+    /// Creates a new FastInput with a given input that implements `Read`,
+    /// wrapping the input in a [`BufReader`] while reading.
+    ///
+    /// Useful for readers where each read is an expensive system call, such
+    /// as unbuffered pipes or network sockets.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use fast_input::FastInput;
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080").unwrap();
+    /// let input = FastInput::with_reader_buffered(stream);
+    /// ```
+    /// For more information, see [`with_reader`].
+    pub fn with_reader_buffered<T: Read>(input: T) -> Self {
+        FastInput::with_reader(BufReader::with_capacity(BUFFER_SIZE, input))
+    }
+
+    /// Creates a new FastInput with a given input that implements `Read`,
+    /// returning any IO error instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    /// use std::fs::File;
+    ///
+    /// fn count_lines(path: &str) -> std::io::Result<usize> {
+    ///     let input = FastInput::try_with_reader(File::open(path)?)?;
+    ///     Ok(input.lines().count())
+    /// }
+    ///
+    /// assert!(count_lines("does/not/exist").is_err());
+    /// ```
+    /// For more information, see [`with_reader`].
+    pub fn try_with_reader<T: Read>(input: T) -> std::io::Result<Self> {
+        Ok(FastInput::from_data(FastInput::try_read_to_end(input, BUFFER_SIZE)?))
+    }
+
+    /// Creates a new FastInput reading each of `readers` to the end, in order,
+    /// as if their contents were a single input.
+    ///
+    /// If the data read so far is non-empty and does not end with a newline,
+    /// a newline is inserted before the next non-empty input, so the last
+    /// line of one input and the first line of the next are never joined.
+    /// No newline is added after the last input, and empty inputs are skipped.
+    /// A UTF-8 byte order mark at the start of each input is skipped.
+    ///
+    /// # Examples
+    ///
+    /// Stitching together sample inputs:
+    /// ```
+    /// use fast_input::FastInput;
+    /// use std::io::Read;
+    ///
+    /// let readers: Vec<Box<dyn Read>> = vec![
+    ///     Box::new("1 2".as_bytes()),
+    ///     Box::new("3 4\n".as_bytes()),
+    ///     Box::new("5 6".as_bytes()),
+    /// ];
+    /// let input = FastInput::from_readers(readers);
+    /// let lines: Vec<_> = input.lines().collect();
+    /// assert_eq!(vec!["1 2", "3 4", "5 6"], lines);
+    /// ```
+    /// # Panics
+    /// If reading any of the inputs fails.
+    pub fn from_readers(readers: Vec<Box<dyn Read>>) -> Self {
+        let mut data = Vec::with_capacity(BUFFER_SIZE);
+        for reader in readers {
+            let next = FastInput::read_to_end(reader, BUFFER_SIZE);
+            let next = FastInput::strip_bom(&next);
+            if next.is_empty() {
+                continue;
             }
-        } else {
-            unsafe {
-                let s = from_utf8_unchecked(&self.data[self.pos.get()..]);
-                self.pos.set(self.data.len());
-                s
+            if data.last().is_some_and(|&b| b != b'\n') {
+                data.push(b'\n');
             }
+            data.extend_from_slice(next);
         }
+        FastInput::from_data(data)
     }
 
-    /// Reads the next line as a single value and parses it.
+    /// Creates a new FastInput reading the file at `path`, returning any IO error.
     ///
     /// # Examples
     ///
-    /// Reading an integer:
+    /// Running against a sample file locally, and standard input otherwise:
     /// ```no_run
-    /// //Input:
-    /// //123
     /// use fast_input::FastInput;
     ///
-    /// let input = FastInput::new();
-    /// let number: i32 = input.next_parsed();
-    /// println!("{}", number);
+    /// let input = match std::env::args().nth(1) {
+    ///     Some(path) => FastInput::from_path(path).unwrap(),
+    ///     None => FastInput::new(),
+    /// };
     /// ```
-    pub fn next_parsed<'a, T: FParse<'a>>(&'a self) -> T {
-        let mut it = self.next_as_iter();
-        it.next().unwrap()
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        Ok(FastInput::from_data(std::fs::read(path)?))
     }
 
+    /// Creates a new FastInput reading all of standard input, and validates that
+    /// the input is UTF-8.
+    ///
+    /// For more information, see [`with_reader_checked`].
+    pub fn new_checked() -> Result<Self, Utf8Error> {
+        FastInput::with_reader_checked(stdin().lock())
+    }
 
+    /// Creates a new FastInput with a given input that implements `Read`, and
+    /// validates that the input is UTF-8.
+    ///
+    /// Validation is done once, after which reading is as fast as with [`with_reader`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let valid = FastInput::with_reader_checked("åäö".as_bytes());
+    /// assert_eq!("åäö", valid.unwrap().next_line());
+    ///
+    /// let latin1 = FastInput::with_reader_checked(&[b'a', 0xe5][..]);
+    /// assert_eq!(1, latin1.err().unwrap().valid_up_to());
+    /// ```
+    pub fn with_reader_checked<T: Read>(input: T) -> Result<Self, Utf8Error> {
+        let data = FastInput::read_to_end(input, BUFFER_SIZE);
+        std::str::from_utf8(&data)?;
+        Ok(FastInput::from_data(data))
+    }
 
+    /// Creates a new FastInput over the given bytes, taking ownership
+    /// without copying.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::from_bytes(b"1 2\n3".to_vec());
+    /// assert_eq!(vec![1, 2], input.next_as_iter().collect::<Vec<u32>>());
+    /// ```
+    pub fn from_bytes(data: Vec<u8>) -> Self {
+        FastInput::from_data(data)
+    }
 
+    /// Creates a new FastInput over a copy of the given string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::from_str("Hello\nWorld");
+    /// assert_eq!("Hello", input.next_line());
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        FastInput::from_data(s.as_bytes().to_vec())
+    }
 
-    /// Reads the next line and returns an iterator over the elements of the line.
+    /// Creates a new FastInput over a memory mapped file.
+    ///
+    /// Instead of copying the file into memory, the file is mapped into the
+    /// address space and read directly, avoiding a copy of huge inputs.
+    /// Requires the `mmap` feature and a 64-bit unix platform.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified (by this or any other process) while the
+    /// FastInput is alive, as that would change the contents of returned `&str`s.
     ///
     /// # Examples
     ///
-    /// Collecting a line into a [`Vec`] of integers.
     /// ```no_run
     /// use fast_input::FastInput;
     ///
-    /// let input = FastInput::new();
-    /// let numbers: Vec<u32> = input.next_as_iter().collect();
-    /// println!("Last line contained {} numbers!", numbers.len());
+    /// let input = unsafe { FastInput::from_file_mmap("huge_input.txt")? };
+    /// let lines = input.lines().count();
+    /// # Ok::<(), std::io::Error>(())
     /// ```
-    /// # Panics
-    /// If there is no more data in the buffer. See [`has_next_line`].
-    pub fn next_as_iter<'a, T: FParse<'a>>(&'a self) -> impl Iterator<Item = T> + '_ {
-        self.next_line().trim().split(' ').map(|x| T::fparse(x))
+    #[cfg(all(feature = "mmap", unix, target_pointer_width = "64"))]
+    pub unsafe fn from_file_mmap<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let mut input = FastInput::from_data(Vec::new());
+        input.mmap = Some(mmap::Mmap::open(path.as_ref())?);
+        Ok(input)
     }
 
-    /// Reads the next line and returns an iterator over the elements (no parsing).
+    /// Creates a new FastInput that reads standard input lazily.
+    ///
+    /// Unlike [`new`], no input is read upon creation. Instead, data is read
+    /// from standard input as the read methods need it, blocking only until
+    /// enough data is available to complete the read. This makes it possible
+    /// to use FastInput for interactive problems, where the judge waits for
+    /// output before sending more input.
+    ///
+    /// Methods that need the entire input, such as [`remaining`], block until EOF.
     ///
     /// # Examples
     ///
-    /// Reading a sentence and printing the individual words:
+    /// Answering queries one at a time:
     /// ```no_run
     /// use fast_input::FastInput;
     ///
-    /// let input = FastInput::new();
-    /// let words = input.next_split();
-    /// for (i, word) in words.enumerate() {
-    ///     println!("Word {} was: {}", i, word);
+    /// let input = FastInput::streaming();
+    /// while let Some(query) = input.try_next_line() {
+    ///     println!("{}", query.len());
     /// }
     /// ```
-    /// # Panics
-    /// If there is no more data in the buffer. See [`has_next_line`].
-    pub fn next_split<'a>(&'a self) -> impl Iterator<Item = &'a str> + '_ {
-        self.next_line().trim().split(' ')
+    pub fn streaming() -> Self {
+        FastInput::from_source(Box::new(BufReader::with_capacity(BUFFER_SIZE, stdin())))
     }
 
-    /// Checks if there is more data available in the buffer.
+    /// Creates a new FastInput that reads `input` lazily.
+    ///
+    /// Combined with [`drain_lines`], this processes inputs larger than the
+    /// available memory line by line. For more information, see [`streaming`].
     ///
     /// # Examples
     ///
-    /// Reading until EOF:
+    /// Counting the errors in a log file:
     /// ```no_run
     /// use fast_input::FastInput;
+    /// use std::fs::File;
     ///
-    /// let input = FastInput::new();
-    /// while input.has_next_line() {
-    ///     println!("{}", input.next_line());
-    /// }
+    /// let mut input = FastInput::streaming_from(File::open("huge.log").unwrap());
+    /// let errors = input.drain_lines().filter(|l| l.contains("ERROR")).count();
+    /// println!("{} errors", errors);
     /// ```
-    pub fn has_next_line(&self) -> bool {
-        self.pos.get() != self.data.len()
+    pub fn streaming_from<T: Read + Send + 'static>(input: T) -> Self {
+        FastInput::from_source(Box::new(BufReader::with_capacity(BUFFER_SIZE, input)))
     }
 
-    fn read_to_end<T: Read>(mut input: T, buffer_size: usize) -> Vec<u8> {
-        let mut data = Vec::with_capacity(buffer_size);
-        input.read_to_end(&mut data).unwrap();
-        data
+    /// Returns a [`FastInputBuilder`] for configuring a new FastInput.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::builder()
+    ///     .delimiter(',')
+    ///     .null_token("NA")
+    ///     .build_from_reader("1,NA,3".as_bytes());
+    /// let values: Vec<Option<u32>> = input.next_as_iter_opt().collect();
+    /// assert_eq!(vec![Some(1), None, Some(3)], values);
+    /// ```
+    pub fn builder() -> FastInputBuilder {
+        FastInputBuilder::new()
     }
 
-    fn next_newline(&self) -> Option<usize> {
-        let mut i = self.pos.get();
-        while i < self.data.len() && self.data[i] != b'\n' {
-            i += 1;
-        }
-        if i < self.data.len() && self.data[i] == b'\n' {
-            Some(i)
-        } else {
-            None
-        }
+    /// Sets the delimiter used to split lines into tokens, the default is `' '`.
+    ///
+    /// The delimiter is used by [`next_split`], [`next_as_iter`] and the
+    /// tuple readers ([`FastParse`]).
+    ///
+    /// Splitting on the delimiter keeps empty fields, so `a,,c` has three fields,
+    /// the second one empty. Parsing an empty field fails like any other invalid
+    /// token, use [`next_as_iter_opt`] for optional fields. This differs from
+    /// [`next_whitespace_split`], [`next_split_by_any`] and the token readers,
+    /// which collapse runs of separators and never produce empty tokens.
+    ///
+    /// Note that trimming (see [`with_trim`]) removes all surrounding whitespace,
+    /// including a whitespace delimiter such as `'\t'`. To keep empty fields at the
+    /// start or end of such lines, disable trimming.
+    ///
+    /// # Examples
+    ///
+    /// Reading comma separated values:
+    /// ```
+    /// use fast_input::{FastInput, FastParse};
+    ///
+    /// let input = FastInput::with_reader("1,2\n3,4,5".as_bytes()).with_delimiter(',');
+    /// let (one, two): (i32, i32) = input.next();
+    /// let rest: Vec<i32> = input.next_as_iter().collect();
+    ///
+    /// assert_eq!((1, 2), (one, two));
+    /// assert_eq!(vec![3, 4, 5], rest);
+    /// ```
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
     }
 
-    /// Returns a (consuming) iterator over all remaining lines.
+    /// Sets whether lines are trimmed before being split into tokens, the default is `true`.
+    ///
+    /// With trimming disabled, leading and trailing delimiters are significant.
+    /// Note that regardless of trimming, consecutive delimiters always produce
+    /// empty tokens (like [`str::split`]), so `" 1  2"` is split into
+    /// `["", "1", "", "2"]` without trimming and `["1", "", "2"]` with trimming.
     ///
     /// # Examples
     ///
-    /// Printing all lines:
-    /// ```rust
+    /// Reading fixed width fields:
+    /// ```
     /// use fast_input::FastInput;
     ///
-    /// let data = "First\nSecond\nThird".as_bytes();
-    /// let input = FastInput::with_reader(data);
-    /// let all_lines: Vec<_> = input.lines().collect();
+    /// let input = FastInput::with_reader(" a b ".as_bytes()).with_trim(false);
+    /// let fields: Vec<_> = input.next_split().collect();
+    /// assert_eq!(vec!["", "a", "b", ""], fields);
+    /// ```
+    pub fn with_trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Sets the token that represents a missing value, the default is the empty token.
+    ///
+    /// See [`next_as_iter_opt`].
+    ///
+    /// # Examples
     ///
-    /// assert_eq!(&all_lines, &["First", "Second", "Third"]);
-    /// assert_eq!(input.has_next_line(), false);
     /// ```
+    /// use fast_input::FastInput;
     ///
-    pub fn lines<'a>(&'a self) -> impl Iterator<Item = &str> + 'a {
-        (0..).take_while(move |_| self.has_next_line())
-            .map(move |_| self.next_line())
+    /// let input = FastInput::with_reader("1 - 3".as_bytes()).with_null_token("-");
+    /// let values: Vec<Option<i32>> = input.next_as_iter_opt().collect();
+    /// assert_eq!(vec![Some(1), None, Some(3)], values);
+    /// ```
+    pub fn with_null_token<S: Into<String>>(mut self, null_token: S) -> Self {
+        self.null_token = null_token.into();
+        self
     }
-}
 
-impl Default for FastInput {
-    fn default() -> Self {
-        Self::new()
+    /// Sets the group separator removed from numbers by [`next_grouped`],
+    /// the default is `','`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1.000.000".as_bytes()).with_group_separator('.');
+    /// assert_eq!(1_000_000u32, input.next_grouped());
+    /// ```
+    pub fn with_group_separator(mut self, separator: char) -> Self {
+        self.group_separator = separator;
+        self
     }
-}
-
-pub trait FastParse<'a, T> {
-    fn next(&'a self) -> T;
-}
 
-impl<'a, T1, T2> FastParse<'a, (T1, T2)> for FastInput
-where
-    T1: FParse<'a>,
-    T2: FParse<'a>
-{
-    /// Reads two elements separated by a space, and returns them parsed as a tuple.
+    /// Sets the byte ending a line, the default is `b'\n'`.
+    ///
+    /// All methods reading, counting or skipping lines (such as [`next_line`],
+    /// [`lines`] and [`line_scanner`]) split the input on `terminator` instead,
+    /// and `\n` becomes an ordinary byte within a line. The token readers,
+    /// such as [`next_token`], still separate tokens by whitespace.
     ///
     /// # Examples
     ///
-    /// Reading an `i32` and a `f64`:
-    /// ```no_run
+    /// Reading records separated by `;`:
+    /// ```
     /// use fast_input::{FastInput, FastParse};
     ///
-    /// let input = FastInput::new();
-    /// let (age, length): (i32, f64) = input.next();
-    /// println!("{} {}", age, length);
+    /// let input = FastInput::with_reader("1 2;3 4;5 6".as_bytes()).with_line_terminator(b';');
+    /// let records: Vec<(u32, u32)> = input.tuples().collect();
+    /// assert_eq!(vec![(1, 2), (3, 4), (5, 6)], records);
     /// ```
     /// # Panics
-    /// If there is no more data in the buffer. See [`has_next_line`].
-    fn next(&'a self) -> (T1, T2) {
-        let mut it = self.next_split();
-        (
-            T1::fparse(it.next().unwrap()),
-            T2::fparse(it.next().unwrap()),
-        )
+    /// If `terminator` is not ASCII, as lines could then end in the middle
+    /// of a UTF-8 character.
+    pub fn with_line_terminator(mut self, terminator: u8) -> Self {
+        assert!(terminator.is_ascii(), "line terminator {:?} is not ASCII", terminator);
+        self.line_terminator = terminator;
+        self.max_line_len.set(None);
+        self
     }
-}
 
-impl<'a, T1, T2, T3> FastParse<'a, (T1, T2, T3)> for FastInput
-where
-    T1: FParse<'a>,
-    T2: FParse<'a>,
-    T3: FParse<'a>
-{
-    /// Reads three elements separated by a space, and returns them as a triple.
+    /// Reads the next line and returns it.
+    ///
+    /// The line terminator (`\n` or `\r\n`) is not included, but any other
+    /// surrounding whitespace is, see [`next_line_trimmed`]. The methods splitting
+    /// a line into elements, such as [`next_split`], [`next_as_iter`] and
+    /// [`FastParse::next`], trim the line first (unless disabled with [`with_trim`]).
     ///
     /// # Panics
-    /// If there is no more data in the buffer. See [`has_next_line`].
-    fn next(&'a self) -> (T1, T2, T3) {
-        let mut it = self.next_split();
-        (
-            T1::fparse(it.next().unwrap()),
-            T2::fparse(it.next().unwrap()),
-            T3::fparse(it.next().unwrap()),
-        )
+    ///
+    /// The function panics if there is no more data in the buffer.
+    /// If you are unsure if there is a next line, see [`has_next_line`].
+    pub fn next_line(&self) -> &str {
+        unsafe { from_utf8_unchecked(self.next_line_bytes()) }
     }
-}
 
-impl<'a, T1, T2, T3, T4> FastParse<'a, (T1, T2, T3, T4)> for FastInput
-where
-    T1: FParse<'a>,
-    T2: FParse<'a>,
-    T3: FParse<'a>,
-    T4: FParse<'a>
-{
-    /// Reads four elements separated by a space, and returns them as a quad-tuple.
+    /// Reads up to the next occurrence of `byte`, and returns the data before it.
+    ///
+    /// The position is advanced past `byte`. If `byte` does not occur in the
+    /// rest of the input, the rest of the input is returned, like [`next_line`].
+    /// Unlike `next_line`, a trailing `\r` is not removed.
+    ///
+    /// # Examples
     ///
+    /// Reading records separated by `;`, which may span several lines:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("a b;c\nd;e".as_bytes());
+    /// assert_eq!("a b", input.next_until(b';'));
+    /// assert_eq!("c\nd", input.next_until(b';'));
+    /// assert_eq!("e", input.next_until(b';'));
+    /// ```
     /// # Panics
-    /// If there is no more data in the buffer. See [`has_next_line`].
-    fn next(&'a self) -> (T1, T2, T3, T4) {
-        let mut it = self.next_split();
-        (
-            T1::fparse(it.next().unwrap()),
-            T2::fparse(it.next().unwrap()),
-            T3::fparse(it.next().unwrap()),
-            T4::fparse(it.next().unwrap()),
-        )
+    /// If `byte` is not ASCII, as the returned data could then end in the
+    /// middle of a UTF-8 character.
+    pub fn next_until(&self, byte: u8) -> &str {
+        assert!(byte.is_ascii(), "delimiter {:?} is not ASCII", byte);
+        let pos = self.pos.get();
+        let (data, next) = match self.next_byte(byte) {
+            Some(i) => (&self.data()[pos..i], i + 1),
+            None => (&self.data()[pos..], self.data().len()),
+        };
+        self.set_pos(next);
+        unsafe { from_utf8_unchecked(data) }
     }
-}
 
-impl<'a, T1, T2, T3, T4, T5> FastParse<'a, (T1, T2, T3, T4, T5)> for FastInput
-where
-    T1: FParse<'a>,
-    T2: FParse<'a>,
-    T3: FParse<'a>,
-    T4: FParse<'a>,
-    T5: FParse<'a>
-{
-    /// Reads five elements separated by a space, and returns them as a quintuple.
+    /// Reads the next line and returns it with leading and trailing whitespace removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
     ///
+    /// let input = FastInput::with_reader("  indented \t\n".as_bytes());
+    /// assert_eq!("indented", input.next_line_trimmed());
+    /// ```
     /// # Panics
     /// If there is no more data in the buffer. See [`has_next_line`].
-    fn next(&'a self) -> (T1, T2, T3, T4, T5) {
-        let mut it = self.next_split();
-        (
-            T1::fparse(it.next().unwrap()),
-            T2::fparse(it.next().unwrap()),
-            T3::fparse(it.next().unwrap()),
-            T4::fparse(it.next().unwrap()),
-            T5::fparse(it.next().unwrap()),
-        )
+    pub fn next_line_trimmed(&self) -> &str {
+        self.next_line().trim()
     }
-}
-
-
-/// Helper trait for parsing.
-/// Mainly used to avoid repeating type constraints.
-pub trait FParse<'a> {
-    /// Parses a type from a string slice
-    fn fparse(s: &'a str) -> Self;
-}
 
-impl<'a, T: FromStr> FParse<'a> for T
-where
-    <T as FromStr>::Err: std::fmt::Debug,
-{
-    fn fparse(s: &'a str) -> Self {
-        s.parse().unwrap()
+    /// Reads the next line and returns it as raw bytes, without treating it as UTF-8.
+    ///
+    /// Returns the same line as [`next_line`], the line terminator
+    /// (`\n` or `\r\n`) is not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader(&[0xff, b'a', b'\n', b'b'][..]);
+    /// assert_eq!(&[0xff, b'a'], input.next_line_bytes());
+    /// assert_eq!(b"b", input.next_line_bytes());
+    /// ```
+    pub fn next_line_bytes(&self) -> &[u8] {
+        let (line, next) = self.line_bounds();
+        self.line_start.set(Some(self.pos.get()));
+        self.pos.set(next);
+        line
     }
-}
 
-/// Allows reading of string slices (`&str`).
-/// The standard library does not provide a `FromStr` implementation
-/// for `&str`. The `Str` type newtypes `&str` and implements `FParse`
-/// and `Deref<Target = &str>`.
-///
-/// # Examples
-///
-/// Reading (name, age, city) triples using `Str` and `FastInput`:
+    /// Moves the position back to the start of the last line read, so that
+    /// it is returned again by the next read.
+    ///
+    /// Applies to the line read by [`next_line`] or any method reading whole
+    /// lines. Only one line can be unread, see [`checkpoint`] for going back further.
+    /// Any other movement of the position after the line was read, such as
+    /// reading a token, [`seek`], [`reset`] or [`restore`], forgets the line.
+    ///
+    /// # Examples
+    ///
+    /// Reading lines until a section header:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1\n2\n[next]\n3".as_bytes());
+    /// let mut numbers = Vec::new();
+    /// while let Some(line) = input.try_next_line() {
+    ///     if line.starts_with('[') {
+    ///         input.unread_line();
+    ///         break;
+    ///     }
+    ///     numbers.push(line.parse::<u32>().unwrap());
+    /// }
+    /// assert_eq!(vec![1, 2], numbers);
+    /// assert_eq!("[next]", input.next_line());
+    /// ```
+    /// # Panics
+    /// If no line has been read since the input was created, the last call
+    /// to `unread_line` or the last other movement of the position.
+    pub fn unread_line(&self) {
+        let start = self.line_start.take().expect("no line to unread");
+        self.pos.set(start);
+    }
+
+    /// Reads the next `n` bytes, including any newlines.
+    ///
+    /// The bytes must end at a character boundary, so that the position
+    /// never ends up in the middle of a UTF-8 character.
+    ///
+    /// # Examples
+    ///
+    /// Reading fixed width records:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("AB12\nCD34\n".as_bytes());
+    /// assert_eq!(b"AB12", input.next_bytes(4));
+    /// assert_eq!(b"\nCD", input.next_bytes(3));
+    /// ```
+    /// # Panics
+    /// If fewer than `n` bytes remain, or if the `n` bytes end in the middle
+    /// of a UTF-8 character. See [`try_next_bytes`].
+    pub fn next_bytes(&self, n: usize) -> &[u8] {
+        self.try_next_bytes(n).unwrap_or_else(|| {
+            panic!("expected {} more bytes ending at a character boundary", n)
+        })
+    }
+
+    /// Reads the next `n` bytes, or returns `None` without consuming
+    /// anything if fewer than `n` bytes remain or if the bytes would end
+    /// in the middle of a UTF-8 character.
+    ///
+    /// For more information, see [`next_bytes`].
+    pub fn try_next_bytes(&self, n: usize) -> Option<&[u8]> {
+        let start = self.pos.get();
+        let end = start.checked_add(n)?;
+        // Reads one byte past `end`, to check that it is a character boundary.
+        while end >= self.data().len() && self.fill() {}
+        if end > self.data().len() || !self.is_char_boundary(end) {
+            return None;
+        }
+        self.set_pos(end);
+        Some(&self.data()[start..end])
+    }
+
+    /// Reads the next `char`, or returns `None` at the end of the input.
+    ///
+    /// Unlike [`Char`], which reads the first `char` of a token, this reads
+    /// every character of the input, including whitespace and newlines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("å\nb".as_bytes());
+    /// let chars: Vec<char> = std::iter::from_fn(|| input.next_char()).collect();
+    /// assert_eq!(vec!['å', '\n', 'b'], chars);
+    /// ```
+    /// # Panics
+    /// If the input at the position is not valid UTF-8.
+    pub fn next_char(&self) -> Option<char> {
+        let pos = self.pos.get();
+        let first = self.scan_from(pos, |_| true)?;
+        let first = self.data()[first];
+        let width = match first.leading_ones() {
+            0 => 1,
+            n => n as usize,
+        };
+        let bytes = self
+            .try_next_bytes(width)
+            .and_then(|bytes| std::str::from_utf8(bytes).ok())
+            .unwrap_or_else(|| panic!("invalid UTF-8 at byte {}", pos));
+        bytes.chars().next()
+    }
+
+    /// Reads the next line and returns it as an owned [`String`].
+    ///
+    /// Useful when the line must outlive further reads, see [`next_line`]
+    /// for the zero-copy variant.
+    pub fn next_line_owned(&self) -> String {
+        self.next_line().to_owned()
+    }
+
+    /// Skips the next line.
+    ///
+    /// Cheaper than calling [`next_line`] and discarding the result.
+    ///
+    /// # Panics
+    /// If there is no more data in the buffer. See [`has_next_line`].
+    pub fn skip_line(&self) {
+        assert!(self.try_skip_lines(1), "no more lines to skip");
+    }
+
+    /// Skips the rest of the current line, including its line terminator.
+    ///
+    /// Does nothing if the position is already at the start of a line, e.g.
+    /// after a line has been read in full.
+    ///
+    /// # Examples
+    ///
+    /// Reading the first token of each line with a [`LineScanner`]:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1 a b\n2 c\n".as_bytes());
+    /// let mut ids = Vec::new();
+    /// while input.has_next_line() {
+    ///     ids.push(input.line_scanner().next::<u32>());
+    ///     input.finish_line();
+    /// }
+    /// assert_eq!(vec![1, 2], ids);
+    /// ```
+    pub fn finish_line(&self) {
+        let pos = self.pos.get();
+        if pos != 0 && self.data()[pos - 1] != self.line_terminator {
+            self.set_pos(self.line_bounds().1);
+        }
+    }
+
+    /// Skips the next `n` lines.
+    ///
+    /// # Examples
+    ///
+    /// Skipping a header:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("# header\n# more header\n42".as_bytes());
+    /// input.skip_lines(2);
+    /// assert_eq!(42, input.next_parsed());
+    /// ```
+    /// # Panics
+    /// If there are fewer than `n` lines remaining.
+    pub fn skip_lines(&self, n: usize) {
+        assert!(self.try_skip_lines(n), "fewer than {} lines to skip", n);
+    }
+
+    /// Skips the next `n` lines, returns `false` if there were fewer
+    /// than `n` lines remaining, in which case all remaining lines are skipped.
+    pub fn try_skip_lines(&self, n: usize) -> bool {
+        for _ in 0..n {
+            if !self.has_next_line() {
+                return false;
+            }
+            let next = self.next_newline().map_or(self.data().len(), |i| i + 1);
+            self.set_pos(next);
+        }
+        true
+    }
+
+    /// Returns the next line without consuming it, or `None` if there
+    /// is no more data.
+    ///
+    /// # Examples
+    ///
+    /// Dispatching on a header line:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("QUERY 1 2\nUPDATE 3".as_bytes());
+    /// while let Some(line) = input.peek_line() {
+    ///     if line.starts_with("QUERY") {
+    ///         assert_eq!("QUERY 1 2", input.next_line());
+    ///     } else {
+    ///         assert_eq!("UPDATE 3", input.next_line());
+    ///     }
+    /// }
+    /// ```
+    pub fn peek_line(&self) -> Option<&str> {
+        if self.has_next_line() {
+            unsafe { Some(from_utf8_unchecked(self.line_bounds().0)) }
+        } else {
+            None
+        }
+    }
+
+    /// Counts the elements of the next line without consuming it, or returns
+    /// 0 if there is no more data.
+    ///
+    /// The line is split like in [`next_split`], so an empty line has
+    /// one (empty) element.
+    ///
+    /// # Examples
+    ///
+    /// Reading lines with an optional third element:
+    /// ```
+    /// use fast_input::{FastInput, FastParse};
+    ///
+    /// let input = FastInput::with_reader("1 2\n3 4 5".as_bytes());
+    /// let mut weights = Vec::new();
+    /// while input.has_next_line() {
+    ///     if input.peek_token_count() == 2 {
+    ///         let (a, b): (u32, u32) = input.next();
+    ///         weights.push((a, b, 1));
+    ///     } else {
+    ///         weights.push(input.next());
+    ///     }
+    /// }
+    /// assert_eq!(vec![(1, 2, 1), (3, 4, 5)], weights);
+    /// ```
+    pub fn peek_token_count(&self) -> usize {
+        self.peek_line()
+            .map_or(0, |line| self.split(line, self.delimiter).count())
+    }
+
+    /// Reads the next line and returns it, or `None` if there is no more data.
+    ///
+    /// Behaves like [`next_line`] but does not panic at the end of the buffer.
+    ///
+    /// # Examples
+    ///
+    /// Reading until EOF:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("First\nSecond".as_bytes());
+    /// let mut count = 0;
+    /// while let Some(_line) = input.try_next_line() {
+    ///     count += 1;
+    /// }
+    /// assert_eq!(2, count);
+    /// assert_eq!(None, input.try_next_line());
+    /// ```
+    pub fn try_next_line(&self) -> Option<&str> {
+        if self.has_next_line() {
+            Some(self.next_line())
+        } else {
+            None
+        }
+    }
+
+    /// Reads the next line as a single value and parses it.
+    ///
+    /// # Examples
+    ///
+    /// Reading an integer:
+    /// ```no_run
+    /// //Input:
+    /// //123
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::new();
+    /// let number: i32 = input.next_parsed();
+    /// println!("{}", number);
+    /// ```
+    pub fn next_parsed<'a, T: FParse<'a>>(&'a self) -> T {
+        let mut it = self.next_as_iter();
+        it.next().unwrap()
+    }
+
+
+
+
+
+    /// Reads the next line and parses the whole (trimmed) line with `FromStr`.
+    ///
+    /// Unlike [`next_parsed`], the line is not split, so types whose textual
+    /// form contains spaces or other separators can be read.
+    ///
+    /// # Examples
+    ///
+    /// Reading a custom type:
+    /// ```
+    /// use fast_input::FastInput;
+    /// use std::str::FromStr;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Point(i32, i32);
+    ///
+    /// impl FromStr for Point {
+    ///     type Err = String;
+    ///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+    ///         let (x, y) = s.split_once(',').ok_or("missing ','")?;
+    ///         Ok(Point(x.parse().map_err(|_| "bad x")?, y.parse().map_err(|_| "bad y")?))
+    ///     }
+    /// }
+    ///
+    /// let input = FastInput::with_reader(" 3,-4 ".as_bytes());
+    /// assert_eq!(Point(3, -4), input.next_line_as());
+    /// ```
+    /// # Panics
+    /// If the line can not be parsed into `T`.
+    pub fn next_line_as<T>(&self) -> T
+    where
+        T: FromStr,
+        <T as FromStr>::Err: std::fmt::Debug,
+    {
+        let line = self.next_line().trim();
+        line.parse()
+            .unwrap_or_else(|e| panic!("failed to parse line {:?}: {:?}", line, e))
+    }
+
+    /// Reads a key from the next line and a value from the line after it,
+    /// parsing each whole (trimmed) line with `FromStr`, see [`next_line_as`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("name\nFerris the crab\n".as_bytes());
+    /// let (key, value): (String, String) = input.next_kv_lines();
+    /// assert_eq!("name", key);
+    /// assert_eq!("Ferris the crab", value);
+    /// ```
+    /// # Panics
+    /// If either line can not be parsed, or if the input ends after the key.
+    pub fn next_kv_lines<K, V>(&self) -> (K, V)
+    where
+        K: FromStr,
+        V: FromStr,
+        <K as FromStr>::Err: std::fmt::Debug,
+        <V as FromStr>::Err: std::fmt::Debug,
+    {
+        let key = self.next_line_as();
+        if !self.has_next_line() {
+            panic!("missing value line after key");
+        }
+        (key, self.next_line_as())
+    }
+
+    /// Reads the next line and returns an iterator over the elements of the line.
+    ///
+    /// # Examples
+    ///
+    /// Collecting a line into a [`Vec`] of integers.
+    /// ```no_run
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::new();
+    /// let numbers: Vec<u32> = input.next_as_iter().collect();
+    /// println!("Last line contained {} numbers!", numbers.len());
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer. See [`has_next_line`].
+    #[allow(mismatched_lifetime_syntaxes)]
+    pub fn next_as_iter<'a, T: FParse<'a>>(&'a self) -> impl Iterator<Item = T> + '_ {
+        self.next_split().map(|x| T::fparse(x))
+    }
+
+    /// Reads the next line and collects its parsed elements into any collection.
+    ///
+    /// Shorthand for `next_as_iter().collect()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    /// use std::collections::{BTreeSet, VecDeque};
+    ///
+    /// let input = FastInput::with_reader("3 1 2\n3 1 3".as_bytes());
+    /// let queue: VecDeque<u32> = input.next_collect();
+    /// let set = input.next_collect::<u32, BTreeSet<_>>();
+    /// assert_eq!(vec![3, 1, 2], Vec::from(queue));
+    /// assert_eq!(vec![1, 3], set.into_iter().collect::<Vec<_>>());
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer. See [`has_next_line`].
+    pub fn next_collect<'a, T: FParse<'a>, C: FromIterator<T>>(&'a self) -> C {
+        self.next_as_iter().collect()
+    }
+
+    /// Reads the next line and returns an iterator over the elements of the line,
+    /// where elements equal to the null token are read as `None`.
+    ///
+    /// The null token is empty by default, and can be set using [`with_null_token`].
+    /// Note that `Option<T>` can not implement [`FParse`] directly, as that would
+    /// conflict with the implementation for all `FromStr` types.
+    ///
+    /// # Examples
+    ///
+    /// Reading a sparse row, where missing values are left empty:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1,,3".as_bytes()).with_delimiter(',');
+    /// let values: Vec<Option<u32>> = input.next_as_iter_opt().collect();
+    /// assert_eq!(vec![Some(1), None, Some(3)], values);
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer. See [`has_next_line`].
+    pub fn next_as_iter_opt<'a, T: FParse<'a>>(&'a self) -> impl Iterator<Item = Option<T>> + 'a {
+        self.next_split()
+            .map(move |x| if x == self.null_token { None } else { Some(T::fparse(x)) })
+    }
+
+    /// Reads the next line as a pair separated by `separator`, regardless of the
+    /// configured delimiter.
+    ///
+    /// The line is split at the first `separator` only, so the second element
+    /// may contain further separators.
+    ///
+    /// # Examples
+    ///
+    /// Reading a time of day:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("12:34".as_bytes());
+    /// let (hours, minutes): (u8, u8) = input.next_tuple_by(':');
+    /// assert_eq!((12, 34), (hours, minutes));
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer (see [`has_next_line`]),
+    /// or if the line does not contain `separator`.
+    pub fn next_tuple_by<'a, T1, T2>(&'a self, separator: char) -> (T1, T2)
+    where
+        T1: FParse<'a>,
+        T2: FParse<'a>,
+    {
+        let line = self.next_line();
+        let line = if self.trim { line.trim() } else { line };
+        let mut it = line.splitn(2, separator);
+        let first = it.next().unwrap();
+        let second = it.next().unwrap_or_else(|| {
+            panic!("separator {:?} not found in line {:?}", separator, line)
+        });
+        (T1::fparse(first), T2::fparse(second))
+    }
+
+    /// Reads the next line as a pair, where missing elements are `None`.
+    ///
+    /// Empty elements, such as those of a blank line, are also `None`. At the
+    /// end of the input, `(None, None)` is returned.
+    ///
+    /// # Examples
+    ///
+    /// Reading a ragged last line:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1 2\n3".as_bytes());
+    /// assert_eq!((Some(1), Some(2)), input.next_tuple_opt::<u8, u8>());
+    /// assert_eq!((Some(3), None), input.next_tuple_opt::<u8, u8>());
+    /// assert_eq!((None, None), input.next_tuple_opt::<u8, u8>());
+    ///
+    /// let input = FastInput::with_reader("\n".as_bytes());
+    /// assert_eq!((None, None), input.next_tuple_opt::<u8, u8>());
+    /// ```
+    /// # Panics
+    /// If an element is present but can not be parsed.
+    pub fn next_tuple_opt<'a, T1, T2>(&'a self) -> (Option<T1>, Option<T2>)
+    where
+        T1: FParse<'a>,
+        T2: FParse<'a>,
+    {
+        match self.try_next_line() {
+            Some(line) => {
+                let mut it = self.split(line, self.delimiter);
+                let mut next = || it.next().filter(|s| !s.is_empty());
+                (next().map(T1::fparse), next().map(T2::fparse))
+            }
+            None => (None, None),
+        }
+    }
+
+    /// Reads the next line and groups its elements into pairs.
+    ///
+    /// Empty elements are skipped, so an empty line gives an empty `Vec`.
+    ///
+    /// # Examples
+    ///
+    /// Reading an edge list given on a single line:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1 2 3 4 5 6".as_bytes());
+    /// let edges: Vec<(u32, u32)> = input.next_tuple_vec();
+    /// assert_eq!(vec![(1, 2), (3, 4), (5, 6)], edges);
+    /// ```
+    /// # Panics
+    /// If the line has an odd number of elements, or if an element can not be parsed.
+    pub fn next_tuple_vec<'a, T1, T2>(&'a self) -> Vec<(T1, T2)>
+    where
+        T1: FParse<'a>,
+        T2: FParse<'a>,
+    {
+        let line = self.next_line();
+        let mut it = self.split(line, self.delimiter).filter(|s| !s.is_empty());
+        let mut pairs = Vec::new();
+        while let Some(first) = it.next() {
+            let second = it.next().unwrap_or_else(|| {
+                panic!("odd number of elements ({}) in line {:?}", 2 * pairs.len() + 1, line)
+            });
+            pairs.push((T1::fparse(first), T2::fparse(second)));
+        }
+        pairs
+    }
+
+    /// Reads the next line and collects its parsed elements into a [`HashSet`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("3 1 3 2".as_bytes());
+    /// let set = input.next_set::<u32>();
+    /// assert_eq!(3, set.len());
+    /// assert!(set.contains(&2));
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer. See [`has_next_line`].
+    pub fn next_set<'a, T: FParse<'a> + Eq + Hash>(&'a self) -> HashSet<T> {
+        self.next_as_iter().collect()
+    }
+
+    /// Reads the next line as alternating keys and values and collects them
+    /// into a [`HashMap`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("apple 3 pear 5".as_bytes());
+    /// let map = input.next_map::<String, u32>();
+    /// assert_eq!(Some(&5), map.get("pear"));
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer (see [`has_next_line`]),
+    /// or if the line has an odd number of elements.
+    pub fn next_map<'a, K, V>(&'a self) -> HashMap<K, V>
+    where
+        K: FParse<'a> + Eq + Hash,
+        V: FParse<'a>,
+    {
+        let mut it = self.next_split();
+        let mut map = HashMap::new();
+        while let Some(key) = it.next() {
+            let value = it.next().unwrap_or_else(|| {
+                panic!("odd number of elements on line, key {:?} has no value", key)
+            });
+            map.insert(K::fparse(key), V::fparse(value));
+        }
+        map
+    }
+
+    /// Reads the next line and parses its first `N` elements into an array.
+    ///
+    /// Elements beyond the first `N` are ignored.
+    ///
+    /// # Examples
+    ///
+    /// Reading a coordinate triple:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1 2 3".as_bytes());
+    /// let [x, y, z]: [i32; 3] = input.next_array();
+    /// assert_eq!((1, 2, 3), (x, y, z));
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer (see [`has_next_line`]),
+    /// or if the line has fewer than `N` elements.
+    pub fn next_array<'a, T: FParse<'a>, const N: usize>(&'a self) -> [T; N] {
+        let mut it = self.next_split();
+        std::array::from_fn(|_| T::fparse(it.next().expect("too few elements on line")))
+    }
+
+    /// Reads the next line and parses its first `N` elements into an array,
+    /// returns `None` if there is no more data or the line has fewer than `N` elements.
+    ///
+    /// The line is consumed even if it has too few elements.
+    /// For more information, see [`next_array`].
+    pub fn try_next_array<'a, T: FParse<'a>, const N: usize>(&'a self) -> Option<[T; N]> {
+        let mut it = self.split(self.try_next_line()?, self.delimiter);
+        let tokens: [Option<&str>; N] = std::array::from_fn(|_| it.next());
+        if tokens.iter().any(Option::is_none) {
+            return None;
+        }
+        Some(tokens.map(|token| T::fparse(token.unwrap())))
+    }
+
+    /// Reads `rows` lines and parses the elements of each line into a grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1 2 3\n4 5 6".as_bytes());
+    /// let grid: Vec<Vec<u32>> = input.next_grid(2);
+    /// assert_eq!(vec![vec![1, 2, 3], vec![4, 5, 6]], grid);
+    /// ```
+    /// # Panics
+    /// If there are fewer than `rows` lines in the buffer.
+    pub fn next_grid<'a, T: FParse<'a>>(&'a self, rows: usize) -> Vec<Vec<T>> {
+        (0..rows)
+            .map(|_| {
+                assert!(self.has_next_line(), "expected {} rows", rows);
+                self.next_as_iter().collect()
+            })
+            .collect()
+    }
+
+    /// Reads the dimensions `R C` from the next line, followed by `R` lines of
+    /// `C` elements each, and parses them into a grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("2 3\n1 2 3\n4 5 6".as_bytes());
+    /// let matrix: Vec<Vec<u32>> = input.next_matrix();
+    /// assert_eq!(vec![vec![1, 2, 3], vec![4, 5, 6]], matrix);
+    /// ```
+    /// # Panics
+    /// If the dimensions can not be read, if there are fewer than `R` lines
+    /// following them, or if a row does not have exactly `C` elements.
+    pub fn next_matrix<'a, T: FParse<'a>>(&'a self) -> Vec<Vec<T>> {
+        let (rows, cols): (usize, usize) = self.next();
+        (0..rows)
+            .map(|row| {
+                assert!(self.has_next_line(), "expected {} rows", rows);
+                let values: Vec<T> = self.next_as_iter().collect();
+                assert!(
+                    values.len() == cols,
+                    "row {} has {} elements, expected {}",
+                    row,
+                    values.len(),
+                    cols
+                );
+                values
+            })
+            .collect()
+    }
+
+    /// Reads the next line into a `Vec` of its characters.
+    ///
+    /// The line is not trimmed, like [`next_line`], so surrounding spaces are
+    /// included. Use [`next_line_trimmed`] if they should be dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("abc\r\n".as_bytes());
+    /// let mut chars = input.next_chars();
+    /// chars.swap(0, 2);
+    /// assert_eq!(vec!['c', 'b', 'a'], chars);
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer. See [`has_next_line`].
+    pub fn next_chars(&self) -> Vec<char> {
+        self.next_line().chars().collect()
+    }
+
+    /// Reads the next line as a row of flags, where each character equal to
+    /// `true_char` is `true` and any other character is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("YNYYN\n".as_bytes());
+    /// assert_eq!(vec![true, false, true, true, false], input.next_flag_row('Y'));
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer. See [`has_next_line`].
+    pub fn next_flag_row(&self, true_char: char) -> Vec<bool> {
+        self.next_line().chars().map(|c| c == true_char).collect()
+    }
+
+    /// Reads `rows` lines into a grid of characters, each `char` being one cell.
+    ///
+    /// Unlike [`next_grid`], the lines are not split on the delimiter,
+    /// so spaces are cells too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("#.#\n. #".as_bytes());
+    /// let grid = input.next_char_grid(2);
+    /// assert_eq!(vec![vec!['#', '.', '#'], vec!['.', ' ', '#']], grid);
+    /// ```
+    /// # Panics
+    /// If there are fewer than `rows` lines in the buffer.
+    pub fn next_char_grid(&self, rows: usize) -> Vec<Vec<char>> {
+        (0..rows)
+            .map(|_| {
+                assert!(self.has_next_line(), "expected {} rows", rows);
+                self.next_line().chars().collect()
+            })
+            .collect()
+    }
+
+    /// Reads lines up to the next blank line or the end of the input, and
+    /// returns them. The blank line is consumed but not returned.
+    ///
+    /// Lines containing only whitespace are considered blank. If the next line
+    /// is blank, or there is no more data, an empty `Vec` is returned.
+    ///
+    /// # Examples
+    ///
+    /// Reading records separated by blank lines:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("a\nb\n\nc\n".as_bytes());
+    /// assert_eq!(vec!["a", "b"], input.next_paragraph());
+    /// assert_eq!(vec!["c"], input.next_paragraph());
+    /// assert!(!input.has_next_line());
+    /// ```
+    pub fn next_paragraph(&self) -> Vec<&str> {
+        let mut lines = Vec::new();
+        while let Some(line) = self.try_next_line() {
+            if line.trim().is_empty() {
+                break;
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// Reads the next line and returns an iterator over the elements (no parsing).
+    ///
+    /// # Examples
+    ///
+    /// Reading a sentence and printing the individual words:
+    /// ```no_run
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::new();
+    /// let words = input.next_split();
+    /// for (i, word) in words.enumerate() {
+    ///     println!("Word {} was: {}", i, word);
+    /// }
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer. See [`has_next_line`].
+    #[allow(mismatched_lifetime_syntaxes, clippy::needless_lifetimes)]
+    pub fn next_split<'a>(&'a self) -> impl Iterator<Item = &'a str> + '_ {
+        self.next_split_by(self.delimiter)
+    }
+
+    /// Reads the next line and stores its elements in `buf`, replacing its
+    /// previous contents.
+    ///
+    /// Splits like [`next_split`], but reusing `buf` avoids allocating a new
+    /// `Vec` for every line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("a b\nc d e".as_bytes());
+    /// let mut buf = Vec::new();
+    /// let mut lengths = Vec::new();
+    /// while input.has_next_line() {
+    ///     input.next_split_into(&mut buf);
+    ///     lengths.push(buf.len());
+    /// }
+    /// assert_eq!(vec![2, 3], lengths);
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer. See [`has_next_line`].
+    pub fn next_split_into<'a>(&'a self, buf: &mut Vec<&'a str>) {
+        buf.clear();
+        buf.extend(self.next_split());
+    }
+
+    /// Reads the next line and returns its elements as owned [`String`]s.
+    ///
+    /// Allocates one `String` per element, in addition to the `Vec`. Useful when
+    /// the elements must outlive the `FastInput`, see [`next_split`] for the
+    /// zero-copy variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let header = {
+    ///     let input = FastInput::with_reader("name age\nAda 36".as_bytes());
+    ///     input.next_split_owned()
+    /// };
+    /// assert_eq!(vec!["name", "age"], header);
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer. See [`has_next_line`].
+    pub fn next_split_owned(&self) -> Vec<String> {
+        self.next_split().map(str::to_owned).collect()
+    }
+
+    /// Reads the next line and returns an iterator over the elements separated
+    /// by any run of whitespace (no parsing).
+    ///
+    /// Unlike [`next_split`], consecutive spaces and tabs never produce empty elements.
+    ///
+    /// # Examples
+    ///
+    /// Reading padded numbers:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("  1    2 \t3 ".as_bytes());
+    /// let numbers: Vec<_> = input.next_whitespace_split().collect();
+    /// assert_eq!(vec!["1", "2", "3"], numbers);
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer. See [`has_next_line`].
+    pub fn next_whitespace_split(&self) -> impl Iterator<Item = &str> + '_ {
+        self.next_line().split_ascii_whitespace()
+    }
+
+    /// Reads the next line and returns an iterator over the elements separated
+    /// by `delimiter` (no parsing).
+    ///
+    /// Like [`str::split`], consecutive delimiters produce empty elements.
+    /// The line is trimmed before splitting, unless disabled with [`with_trim`].
+    ///
+    /// # Examples
+    ///
+    /// Reading a line of tab separated values:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("a\tb\t\tc".as_bytes());
+    /// let values: Vec<_> = input.next_split_by('\t').collect();
+    /// assert_eq!(vec!["a", "b", "", "c"], values);
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer. See [`has_next_line`].
+    pub fn next_split_by(&self, delimiter: char) -> impl Iterator<Item = &str> + '_ {
+        self.split(self.next_line(), delimiter)
+    }
+
+    /// Reads the next line and returns an iterator over the elements separated
+    /// by any run of the characters in `separators` (no parsing).
+    ///
+    /// Unlike [`next_split_by`], empty elements are skipped.
+    ///
+    /// # Examples
+    ///
+    /// Reading a comma separated list with spaces:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1, 2,3 ,  4".as_bytes());
+    /// let values: Vec<_> = input.next_split_by_any(&[',', ' ']).collect();
+    /// assert_eq!(vec!["1", "2", "3", "4"], values);
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer. See [`has_next_line`].
+    pub fn next_split_by_any<'a>(
+        &'a self,
+        separators: &'a [char],
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        self.next_line()
+            .split(separators)
+            .filter(|token| !token.is_empty())
+    }
+
+    /// Reads the next line and returns an iterator over at most `n` elements
+    /// (no parsing), like [`str::splitn`]. The last element contains the
+    /// remainder of the line, including any delimiters.
+    ///
+    /// # Examples
+    ///
+    /// Reading a command and its argument:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("say hello world\n".as_bytes());
+    /// let parts: Vec<_> = input.next_splitn(2).collect();
+    /// assert_eq!(vec!["say", "hello world"], parts);
+    /// ```
+    /// # Panics
+    /// If there is no more data in the buffer. See [`has_next_line`].
+    pub fn next_splitn(&self, n: usize) -> impl Iterator<Item = &str> + '_ {
+        let line = self.next_line();
+        let line = if self.trim { line.trim() } else { line };
+        line.splitn(n, self.delimiter)
+    }
+
+    /// Checks if there is more data available in the buffer.
+    ///
+    /// # Examples
+    ///
+    /// Reading until EOF:
+    /// ```no_run
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::new();
+    /// while input.has_next_line() {
+    ///     println!("{}", input.next_line());
+    /// }
+    /// ```
+    pub fn has_next_line(&self) -> bool {
+        self.pos.get() != self.data().len() || self.fill()
+    }
+
+    /// Checks if any of the remaining lines is non-empty.
+    ///
+    /// Lines containing only whitespace count as empty. Unlike [`has_next_line`],
+    /// this is `false` if only blank lines (or a trailing `\n` followed by
+    /// whitespace) remain, so it avoids an extra iteration on an empty line at the
+    /// end of the input. Blank lines before a non-empty line are not skipped,
+    /// and are still returned by [`next_line`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1\n\n2\n \n\n".as_bytes());
+    /// let mut lines = Vec::new();
+    /// while input.has_next_nonempty_line() {
+    ///     lines.push(input.next_line());
+    /// }
+    /// assert_eq!(vec!["1", "", "2"], lines);
+    /// assert!(input.has_next_line());
+    /// ```
+    pub fn has_next_nonempty_line(&self) -> bool {
+        let terminator = self.line_terminator;
+        self.scan_from(self.pos.get(), |&b| !b.is_ascii_whitespace() && b != terminator)
+            .is_some()
+    }
+
+    /// Reads the next whitespace separated token and parses it.
+    ///
+    /// Unlike the line based methods, tokens are read independently of the
+    /// line structure: any run of spaces, tabs and newlines separates two tokens.
+    /// After reading, the position is directly after the token, so a following
+    /// call to [`next_line`] returns the remainder of the token's line.
+    ///
+    /// # Examples
+    ///
+    /// Reading numbers spread over several lines:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1 2\n\n  3\t4\n".as_bytes());
+    /// let mut sum = 0;
+    /// while input.has_next_token() {
+    ///     sum += input.next_token::<i32>();
+    /// }
+    /// assert_eq!(10, sum);
+    /// ```
+    /// # Panics
+    /// If there are no more tokens in the buffer. See [`has_next_token`].
+    pub fn next_token<'a, T: FParse<'a>>(&'a self) -> T {
+        T::fparse(self.next_token_str().expect("no more tokens in input"))
+    }
+
+    /// Reads the next whitespace separated word.
+    ///
+    /// Like [`next_token`], newlines separate words, but the position is
+    /// advanced past the word and the single separator following it. Reading
+    /// the last word of a line thus also consumes its newline, and a following
+    /// call to [`next_line`] returns the next line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("3 add\nsome text\n".as_bytes());
+    /// let count: u32 = input.next_word().parse().unwrap();
+    /// assert_eq!(3, count);
+    /// assert_eq!("add", input.next_word());
+    /// assert_eq!("some text", input.next_line());
+    /// ```
+    /// # Panics
+    /// If there are no more words in the buffer. See [`has_next_token`].
+    pub fn next_word(&self) -> &str {
+        let (start, end) = self.next_token_bounds().expect("no more tokens in input");
+        let mut next = end;
+        if self.scan_from(next, |_| true).is_some() {
+            next += 1;
+            let crlf = self.data()[end] == b'\r' && self.scan_from(next, |_| true).is_some();
+            if crlf && self.data()[next] == b'\n' {
+                next += 1;
+            }
+        }
+        self.set_pos(next);
+        unsafe { from_utf8_unchecked(&self.data()[start..end]) }
+    }
+
+    /// Reads the next whitespace separated token and parses it, or returns
+    /// `None` if there are no more tokens.
+    ///
+    /// For more information, see [`next_token`].
+    pub fn try_next_token<'a, T: FParse<'a>>(&'a self) -> Option<T> {
+        self.next_token_str().map(T::fparse)
+    }
+
+    /// Reads the next whitespace separated token as an integer.
+    ///
+    /// Faster than [`next_token`] for integers, as the digits are parsed
+    /// directly from the input bytes without going through `FromStr`.
+    /// The token may start with a `-` or `+` sign.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("-12 +7\n18446744073709551615".as_bytes());
+    /// let a: i32 = input.next_int();
+    /// let b: i64 = input.next_int();
+    /// let c: u64 = input.next_int();
+    /// assert_eq!((-12, 7, u64::MAX), (a, b, c));
+    /// ```
+    /// # Panics
+    /// If there are no more tokens, or if the token is not an integer
+    /// in the range of `T`.
+    pub fn next_int<T: FastInt>(&self) -> T {
+        let (start, end) = self.next_token_bounds().expect("no more tokens in input");
+        self.set_pos(end);
+        let token = &self.data()[start..end];
+        let (negative, digits) = match token[0] {
+            b'-' => (true, &token[1..]),
+            b'+' => (false, &token[1..]),
+            _ => (false, token),
+        };
+        T::from_digits(digits, negative).unwrap_or_else(|| {
+            panic!(
+                "invalid integer {:?} for type {}",
+                String::from_utf8_lossy(token),
+                type_name::<T>()
+            )
+        })
+    }
+
+    /// Reads the next integer in the input, skipping any bytes before it.
+    ///
+    /// Unlike [`next_int`], the line structure and separators are ignored:
+    /// everything up to the next digit, or `-` followed by a digit, is skipped,
+    /// and the integer ends at the first non-digit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("[1,-2],\n(x=30)".as_bytes());
+    /// let values: Vec<i32> = (0..3).map(|_| input.next_int_any()).collect();
+    /// assert_eq!(vec![1, -2, 30], values);
+    /// ```
+    /// # Panics
+    /// If there are no more integers in the input, or if the integer is
+    /// not in the range of `T`.
+    pub fn next_int_any<T: FastInt>(&self) -> T {
+        let mut from = self.pos.get();
+        let (negative, start) = loop {
+            let i = self
+                .scan_from(from, |b| b.is_ascii_digit() || *b == b'-')
+                .expect("no more integers in input");
+            if self.data()[i] != b'-' {
+                break (false, i);
+            }
+            from = i + 1;
+            if self.scan_from(from, |_| true).is_some() && self.data()[from].is_ascii_digit() {
+                break (true, from);
+            }
+        };
+        let end = self
+            .scan_from(start, |b| !b.is_ascii_digit())
+            .unwrap_or_else(|| self.data().len());
+        self.set_pos(end);
+        T::from_digits(&self.data()[start..end], negative).unwrap_or_else(|| {
+            panic!(
+                "invalid integer {:?} for type {}",
+                String::from_utf8_lossy(&self.data()[start..end]),
+                type_name::<T>()
+            )
+        })
+    }
+
+    /// Reads the next whitespace separated token as a floating point number.
+    ///
+    /// Faster than [`next_token`] for floats. Decimals whose digits fit exactly
+    /// in the float type (at most 2^53 for `f64` and 2^24 for `f32`, about 15
+    /// and 7 significant digits) and with a small exponent (at most 22 for `f64`
+    /// and 10 for `f32`, after accounting for the fraction digits) are converted
+    /// directly from the input bytes, which covers typical coordinates and
+    /// measurements. Other tokens, e.g. with long mantissas, extreme exponents,
+    /// subnormal results, `inf` or `NaN`, fall back to `str::parse`. The result
+    /// is therefore always the same as that of `str::parse`, but tokens that
+    /// fall back are slower to read than with [`next_token`], which is common
+    /// for `f32` (see `benches/float_parse.rs`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("-1.5 2e3\n0.125".as_bytes());
+    /// let a: f64 = input.next_float();
+    /// let b: f32 = input.next_float();
+    /// let c: f64 = input.next_float();
+    /// assert_eq!((-1.5, 2000.0, 0.125), (a, b, c));
+    /// ```
+    /// # Panics
+    /// If there are no more tokens, or if the token is not a valid float.
+    pub fn next_float<T: FastFloat>(&self) -> T {
+        let (start, end) = self.next_token_bounds().expect("no more tokens in input");
+        self.set_pos(end);
+        let token = &self.data()[start..end];
+        T::from_bytes(token).unwrap_or_else(|| {
+            panic!(
+                "invalid float {:?} for type {}",
+                String::from_utf8_lossy(token),
+                type_name::<T>()
+            )
+        })
+    }
+
+    /// Reads the next whitespace separated token as a number with group
+    /// separators, such as `1,000,000`, and parses it.
+    ///
+    /// The separator is configured with [`with_group_separator`], see also
+    /// [`Grouped`] for a separator fixed by the type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1,234,567 -89".as_bytes());
+    /// let a: i64 = input.next_grouped();
+    /// let b: i64 = input.next_grouped();
+    /// assert_eq!((1_234_567, -89), (a, b));
+    /// ```
+    /// # Panics
+    /// If there are no more tokens, or if the token without separators can
+    /// not be parsed into `T`.
+    pub fn next_grouped<T>(&self) -> T
+    where
+        T: FromStr,
+        <T as FromStr>::Err: std::fmt::Debug,
+    {
+        let token = self.next_token_str().expect("no more tokens in input");
+        T::fparse(&remove_group_separator(token, self.group_separator))
+    }
+
+    /// Reads the next whitespace separated token as an integer in the given `radix`.
+    ///
+    /// A `0x`, `0o` or `0b` prefix, after an optional sign, is allowed for
+    /// radix 16, 8 and 2 respectively.
+    ///
+    /// # Examples
+    ///
+    /// Reading a hexadecimal bitmask:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("0xFF 1010".as_bytes());
+    /// let mask: u32 = input.next_radix(16);
+    /// let bits: u8 = input.next_radix(2);
+    /// assert_eq!((255, 10), (mask, bits));
+    /// ```
+    /// # Panics
+    /// If there are no more tokens, if the token is not a valid integer
+    /// in the given radix, or if `radix` is not in the range 2 to 36.
+    pub fn next_radix<T: FromStrRadix>(&self, radix: u32) -> T {
+        let token = self.next_token_str().expect("no more tokens in input");
+        T::fparse_radix(token, radix)
+    }
+
+    /// Reads exactly `n` whitespace separated tokens and parses them into a [`Vec`].
+    ///
+    /// The tokens may be spread over any number of lines, see [`next_token`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("5\n1 2\n3\n4 5".as_bytes());
+    /// let n = input.next_token();
+    /// let numbers: Vec<u32> = input.next_vec(n);
+    /// assert_eq!(vec![1, 2, 3, 4, 5], numbers);
+    /// ```
+    /// # Panics
+    /// If the buffer contains fewer than `n` more tokens.
+    pub fn next_vec<'a, T: FParse<'a>>(&'a self, n: usize) -> Vec<T> {
+        let mut values = Vec::with_capacity(n);
+        for i in 0..n {
+            let token = self.next_token_str().unwrap_or_else(|| {
+                panic!("expected {} tokens but input ended after {}", n, i)
+            });
+            values.push(T::fparse(token));
+        }
+        values
+    }
+
+    /// Returns the next whitespace separated token without consuming it,
+    /// or `None` if there are no more tokens.
+    ///
+    /// # Examples
+    ///
+    /// Dispatching on command words:
+    /// ```
+    /// use fast_input::{FastInput, FastParse, Str};
+    ///
+    /// let input = FastInput::with_reader("add 1 2\nneg 3".as_bytes());
+    /// let mut results = Vec::new();
+    /// while let Some(command) = input.peek_token() {
+    ///     if command == "add" {
+    ///         let (_, a, b): (Str, i32, i32) = input.next();
+    ///         results.push(a + b);
+    ///     } else {
+    ///         let (_, a): (Str, i32) = input.next();
+    ///         results.push(-a);
+    ///     }
+    /// }
+    /// assert_eq!(vec![3, -3], results);
+    /// ```
+    pub fn peek_token(&self) -> Option<&str> {
+        let (start, end) = self.next_token_bounds()?;
+        unsafe { Some(from_utf8_unchecked(&self.data()[start..end])) }
+    }
+
+    /// Classifies the next whitespace separated token without consuming it,
+    /// see [`TokenKind`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::{FastInput, TokenKind};
+    ///
+    /// let input = FastInput::with_reader("-12 3.5e2 abc".as_bytes());
+    /// let mut kinds = Vec::new();
+    /// while input.peek_token_kind() != TokenKind::Empty {
+    ///     kinds.push(input.peek_token_kind());
+    ///     input.next_word();
+    /// }
+    /// assert_eq!(vec![TokenKind::Integer, TokenKind::Float, TokenKind::Word], kinds);
+    /// ```
+    pub fn peek_token_kind(&self) -> TokenKind {
+        match self.next_token_bounds() {
+            Some((start, end)) => TokenKind::of(&self.data()[start..end]),
+            None => TokenKind::Empty,
+        }
+    }
+
+    /// Returns a scanner reading whitespace separated tokens from the current
+    /// line, one at a time.
+    ///
+    /// Unlike the tuple readers, which consume the whole line, the scanner only
+    /// consumes the tokens it reads. The rest of the line is left in the input,
+    /// and can be skipped with [`finish_line`].
+    ///
+    /// # Examples
+    ///
+    /// Reading an operation followed by a varying number of operands:
+    /// ```
+    /// use fast_input::{FastInput, Str};
+    ///
+    /// let input = FastInput::with_reader("sum 1 2 3\nneg 4".as_bytes());
+    /// let mut scanner = input.line_scanner();
+    /// let op: Str = scanner.next();
+    /// assert_eq!("sum", *op);
+    /// let mut sum = 0;
+    /// while let Some(x) = scanner.try_next::<i32>() {
+    ///     sum += x;
+    /// }
+    /// assert_eq!(6, sum);
+    /// ```
+    pub fn line_scanner(&self) -> LineScanner<'_> {
+        LineScanner { input: self }
+    }
+
+    /// Returns a view sharing the buffer, with its own position starting at
+    /// the current position.
+    ///
+    /// The view and the input advance independently, and no data is copied.
+    /// In streaming mode, the rest of the input is read first.
+    ///
+    /// # Examples
+    ///
+    /// Two passes over the same input:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("3\n1\n2\n".as_bytes());
+    /// let first_pass = input.fork();
+    /// let max = first_pass.lines().map(|l| l.parse::<u32>().unwrap()).max();
+    /// assert_eq!(Some(3), max);
+    /// assert_eq!("3", input.next_line());
+    /// ```
+    pub fn fork(&self) -> FastInputView<'_> {
+        while self.fill() {}
+        FastInputView {
+            data: self.data(),
+            pos: Cell::new(self.pos.get()),
+            crlf: self.crlf,
+            line_terminator: self.line_terminator,
+        }
+    }
+
+    /// Checks if there are any more (non-whitespace) tokens in the buffer.
+    pub fn has_next_token(&self) -> bool {
+        self.next_token_bounds().is_some()
+    }
+
+    /// Moves the position back to the start of the buffer.
+    ///
+    /// # Examples
+    ///
+    /// Reading the same input twice:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1 2 3".as_bytes());
+    /// let first: Vec<u32> = input.next_as_iter().collect();
+    /// input.reset();
+    /// let second: Vec<u32> = input.next_as_iter().collect();
+    /// assert_eq!(first, second);
+    /// ```
+    pub fn reset(&self) {
+        self.set_pos(0);
+    }
+
+    /// Moves the position to the byte offset `pos` in the buffer.
+    ///
+    /// Seeking into the middle of a line is allowed, but it is the caller's
+    /// responsibility that the following reads make sense.
+    ///
+    /// # Panics
+    /// If `pos` is larger than the length of the buffer, or if `pos` is in
+    /// the middle of a UTF-8 character.
+    pub fn seek(&self, pos: usize) {
+        while pos >= self.data().len() && self.fill() {}
+        assert!(pos <= self.data().len(), "seek position {} out of bounds", pos);
+        assert!(
+            self.is_char_boundary(pos),
+            "seek position {} is not a character boundary",
+            pos
+        );
+        self.set_pos(pos);
+    }
+
+    /// Returns the current position, as a byte offset (not a `char` offset)
+    /// from the start of the input.
+    ///
+    /// Together with [`seek`], this can be used to save and restore the position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("åäö\nabc".as_bytes());
+    /// input.next_line();
+    /// assert_eq!(7, input.position());
+    /// ```
+    pub fn position(&self) -> usize {
+        self.pos.get()
+    }
+
+    /// Returns the number of bytes consumed so far, the same as [`position`].
+    ///
+    /// Together with [`bytes_total`], this can be used to show progress.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1 2\n3 4\n".as_bytes());
+    /// input.next_line();
+    /// let percent = 100 * input.bytes_consumed() / input.bytes_total();
+    /// assert_eq!(50, percent);
+    /// ```
+    pub fn bytes_consumed(&self) -> usize {
+        self.pos.get()
+    }
+
+    /// Returns the total number of bytes of the input, excluding a byte
+    /// order mark.
+    ///
+    /// In streaming mode, this is only the number of bytes read from the
+    /// source so far, which grows as more input is read.
+    pub fn bytes_total(&self) -> usize {
+        self.data().len()
+    }
+
+    /// Saves the current position, to be restored later with [`restore`].
+    ///
+    /// # Examples
+    ///
+    /// Speculatively reading a line as numbers, falling back to words:
+    /// ```
+    /// use fast_input::{FastInput, TryFastParse};
+    ///
+    /// let input = FastInput::with_reader("1 two 3".as_bytes());
+    /// let checkpoint = input.checkpoint();
+    /// let numbers: Result<(u32, u32, u32), _> = input.try_next();
+    /// if numbers.is_err() {
+    ///     input.restore(checkpoint);
+    /// }
+    /// let words: Vec<_> = input.next_split().collect();
+    /// assert_eq!(vec!["1", "two", "3"], words);
+    /// ```
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint { pos: self.pos.get() }
+    }
+
+    /// Moves the position back to a [`Checkpoint`] saved with [`checkpoint`].
+    ///
+    /// # Panics
+    /// If the checkpoint is out of bounds, which can only happen if it was
+    /// saved from another `FastInput`.
+    pub fn restore(&self, checkpoint: Checkpoint) {
+        self.seek(checkpoint.pos);
+    }
+
+    /// Returns the length of the input in bytes.
+    ///
+    /// In streaming mode, this reads the rest of the input.
+    pub fn len(&self) -> usize {
+        while self.fill() {}
+        self.data().len()
+    }
+
+    /// Checks if the input is empty.
+    pub fn is_empty(&self) -> bool {
+        self.data().is_empty() && !self.fill()
+    }
+
+    /// Returns all remaining (unread) data without advancing the position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("header\n1 2\n3 4".as_bytes());
+    /// input.next_line();
+    /// assert_eq!("1 2\n3 4", input.remaining());
+    /// assert_eq!("1 2", input.next_line());
+    /// ```
+    pub fn remaining(&self) -> &str {
+        while self.fill() {}
+        unsafe { from_utf8_unchecked(&self.data()[self.pos.get()..]) }
+    }
+
+    /// Returns the entire input as bytes, regardless of the position.
+    ///
+    /// The position is not affected, use [`seek`] to continue reading after
+    /// custom parsing. In streaming mode, this reads the rest of the input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1 2\n3".as_bytes());
+    /// input.next_line();
+    /// let digits = input.as_bytes().iter().filter(|b| b.is_ascii_digit()).count();
+    /// assert_eq!(3, digits);
+    /// assert_eq!("3", input.next_line());
+    /// ```
+    pub fn as_bytes(&self) -> &[u8] {
+        while self.fill() {}
+        self.data()
+    }
+
+    /// Returns all remaining (unread) data as bytes, without advancing the position.
+    ///
+    /// For more information, see [`remaining`] and [`as_bytes`].
+    pub fn remaining_bytes(&self) -> &[u8] {
+        &self.as_bytes()[self.pos.get()..]
+    }
+
+    /// Returns the buffered data after the position, like [`BufRead::fill_buf`].
+    ///
+    /// In streaming mode, more data is read only if no data is buffered, so
+    /// the returned slice may not contain the rest of the input. An empty
+    /// slice means that the end of the input has been reached. Use [`consume`]
+    /// to advance the position.
+    ///
+    /// # Examples
+    ///
+    /// Running a custom parser over the input:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("aaab".as_bytes());
+    /// let a_count = input.fill_buf().iter().take_while(|&&b| b == b'a').count();
+    /// input.consume(a_count);
+    /// assert_eq!(3, a_count);
+    /// assert_eq!("b", input.next_line());
+    /// ```
+    pub fn fill_buf(&self) -> &[u8] {
+        if self.pos.get() == self.data().len() {
+            self.fill();
+        }
+        &self.data()[self.pos.get()..]
+    }
+
+    /// Advances the position by `amt` bytes, like [`BufRead::consume`].
+    ///
+    /// # Panics
+    /// If `amt` is larger than the length of the slice returned by [`fill_buf`],
+    /// or if the new position is in the middle of a UTF-8 character.
+    pub fn consume(&self, amt: usize) {
+        let pos = self
+            .pos
+            .get()
+            .checked_add(amt)
+            .filter(|&pos| pos <= self.data().len())
+            .unwrap_or_else(|| panic!("can not consume {} bytes, past the buffered data", amt));
+        if pos == self.data().len() {
+            // The next byte is needed to check for a character boundary.
+            self.fill();
+        }
+        assert!(
+            self.is_char_boundary(pos),
+            "can not consume {} bytes, position {} is not a character boundary",
+            amt,
+            pos
+        );
+        self.set_pos(pos);
+    }
+
+    /// Returns the length in bytes of the longest line in the input, excluding
+    /// the line terminator, regardless of the position.
+    ///
+    /// The length is computed on the first call and cached. In streaming mode,
+    /// this reads the rest of the input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("ab\r\nabcd\n\nabc".as_bytes());
+    /// assert_eq!(4, input.max_line_len());
+    /// ```
+    pub fn max_line_len(&self) -> usize {
+        if let Some(len) = self.max_line_len.get() {
+            return len;
+        }
+        let data = self.as_bytes();
+        let line_len = |line: &[u8]| {
+            if self.crlf {
+                FastInput::strip_cr(line).len()
+            } else {
+                line.len()
+            }
+        };
+        let mut max = 0;
+        let mut start = 0;
+        while let Some(i) = memchr(self.line_terminator, &data[start..]) {
+            max = max.max(line_len(&data[start..start + i]));
+            start += i + 1;
+        }
+        let max = max.max(line_len(&data[start..]));
+        self.max_line_len.set(Some(max));
+        max
+    }
+
+    /// Returns the number of remaining lines, without advancing the position.
+    ///
+    /// This is the number of lines [`lines`] would return.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("3\na\nb\nc\n".as_bytes());
+    /// let n: usize = input.next_parsed();
+    /// assert_eq!(n, input.remaining_lines());
+    /// assert_eq!(4, input.total_lines());
+    /// ```
+    pub fn remaining_lines(&self) -> usize {
+        self.count_lines(self.remaining().as_bytes())
+    }
+
+    /// Returns the total number of lines in the input, regardless of the position.
+    pub fn total_lines(&self) -> usize {
+        while self.fill() {}
+        self.count_lines(self.data())
+    }
+
+    /// Returns the 1-based number of the line containing the current position.
+    ///
+    /// Counts the newlines before the position, so this takes time linear in
+    /// the position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1 2\n3 x\n".as_bytes());
+    /// input.next_line();
+    /// let line = input.current_line_number();
+    /// if let Err(e) = input.next_line().parse::<u32>() {
+    ///     println!("parse error on line {}: {}", line, e);
+    /// }
+    /// assert_eq!(2, line);
+    /// ```
+    pub fn current_line_number(&self) -> usize {
+        let before = &self.data()[..self.pos.get()];
+        before.iter().filter(|&&b| b == self.line_terminator).count() + 1
+    }
+
+    /// Bounds the number of remaining lines without scanning the input, as
+    /// every line takes at least one byte.
+    fn lines_size_hint(&self) -> (usize, Option<usize>) {
+        let rest = self.data().len() - self.pos.get();
+        let lower = usize::from(rest != 0);
+        if self.source.borrow().is_some() {
+            // More data may arrive.
+            (lower, None)
+        } else {
+            (lower, Some(rest))
+        }
+    }
+
+    fn count_lines(&self, data: &[u8]) -> usize {
+        let terminators = data.iter().filter(|&&b| b == self.line_terminator).count();
+        match data.last() {
+            Some(&b) if b != self.line_terminator => terminators + 1,
+            _ => terminators,
+        }
+    }
+
+    fn next_token_str(&self) -> Option<&str> {
+        let (start, end) = self.next_token_bounds()?;
+        self.set_pos(end);
+        unsafe { Some(from_utf8_unchecked(&self.data()[start..end])) }
+    }
+
+    fn next_token_bounds(&self) -> Option<(usize, usize)> {
+        let start = self.scan_from(self.pos.get(), |b| !b.is_ascii_whitespace())?;
+        let end = self
+            .scan_from(start, |b| b.is_ascii_whitespace())
+            .unwrap_or_else(|| self.data().len());
+        Some((start, end))
+    }
+
+    /// Like `next_token_bounds`, but does not look past the end of the current line.
+    fn line_token_bounds(&self) -> Option<(usize, usize)> {
+        let terminator = self.line_terminator;
+        let start = self.scan_from(self.pos.get(), |&b| {
+            !matches!(b, b' ' | b'\t' | b'\r' | b'\n') || b == terminator
+        })?;
+        if self.data()[start] == terminator {
+            return None;
+        }
+        let end = self
+            .scan_from(start, |&b| b.is_ascii_whitespace() || b == terminator)
+            .unwrap_or_else(|| self.data().len());
+        Some((start, end))
+    }
+
+    /// Finds the first byte at or after `from` matching `pred`, reading more data if needed.
+    fn scan_from<P: Fn(&u8) -> bool>(&self, mut from: usize, pred: P) -> Option<usize> {
+        loop {
+            if let Some(i) = self.data()[from..].iter().position(&pred) {
+                return Some(from + i);
+            }
+            from = self.data().len();
+            if !self.fill() {
+                return None;
+            }
+        }
+    }
+
+    fn from_data(data: Vec<u8>) -> Self {
+        FastInput {
+            start: FastInput::bom_len(&data),
+            data: UnsafeCell::new(data),
+            pos: Cell::new(0),
+            delimiter: ' ',
+            trim: true,
+            crlf: true,
+            line_terminator: b'\n',
+            null_token: String::new(),
+            group_separator: ',',
+            max_line_len: Cell::new(None),
+            line_start: Cell::new(None),
+            source: RefCell::new(None),
+            retired: RefCell::new(Vec::new()),
+            #[cfg(all(feature = "mmap", unix, target_pointer_width = "64"))]
+            mmap: None,
+        }
+    }
+
+    fn from_source(source: Box<dyn BufRead + Send>) -> Self {
+        let input = FastInput::from_data(Vec::with_capacity(BUFFER_SIZE));
+        *input.source.borrow_mut() = Some(Box::new(SkipBom::new(source)));
+        input
+    }
+
+    /// Drops consumed data in streaming mode, moving the position to 0.
+    fn compact(&mut self) {
+        let pos = self.pos.get();
+        if self.source.get_mut().is_none() || pos < BUFFER_SIZE {
+            return;
+        }
+        self.retired.get_mut().clear();
+        self.data.get_mut().drain(..self.start + pos);
+        self.start = 0;
+        self.line_start.set(None);
+        self.pos.set(0);
+    }
+
+    fn data(&self) -> &[u8] {
+        #[cfg(all(feature = "mmap", unix, target_pointer_width = "64"))]
+        {
+            if let Some(map) = &self.mmap {
+                return FastInput::strip_bom(map.as_slice());
+            }
+        }
+        // Safety: `data` is only mutated by `append`, which never moves,
+        // overwrites or frees bytes that have already been handed out.
+        unsafe { &(&*self.data.get())[self.start..] }
+    }
+
+    /// Reads more data from the source in streaming mode.
+    /// Returns `false` if no more data could be read.
+    fn fill(&self) -> bool {
+        let mut source = self.source.borrow_mut();
+        let reader = match source.as_mut() {
+            Some(reader) => reader,
+            None => return false,
+        };
+        let read = loop {
+            match reader.fill_buf() {
+                Ok(buf) => {
+                    self.append(buf);
+                    break buf.len();
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => panic!("failed to read input: {}", e),
+            }
+        };
+        if read == 0 {
+            *source = None;
+            return false;
+        }
+        reader.consume(read);
+        true
+    }
+
+    fn append(&self, bytes: &[u8]) {
+        // Safety: slices returned by earlier reads point into the heap buffer of
+        // `data`. New bytes are only written to spare capacity, and when the
+        // buffer is full it is moved to `retired` instead of being reallocated,
+        // so those slices stay valid for the lifetime of `self`.
+        let data = unsafe { &mut *self.data.get() };
+        if data.capacity() - data.len() < bytes.len() {
+            let capacity = (2 * data.capacity()).max(data.len() + bytes.len());
+            let mut grown = Vec::with_capacity(capacity);
+            grown.extend_from_slice(data);
+            self.retired.borrow_mut().push(std::mem::replace(data, grown));
+        }
+        data.extend_from_slice(bytes);
+    }
+
+    fn read_to_end<T: Read>(input: T, buffer_size: usize) -> Vec<u8> {
+        FastInput::try_read_to_end(input, buffer_size).unwrap()
+    }
+
+    fn try_read_to_end<T: Read>(mut input: T, buffer_size: usize) -> std::io::Result<Vec<u8>> {
+        let mut data = Vec::with_capacity(buffer_size);
+        input.read_to_end(&mut data)?;
+        Ok(data)
+    }
+
+    fn split<'a>(&self, line: &'a str, delimiter: char) -> std::str::Split<'a, char> {
+        if self.trim {
+            line.trim().split(delimiter)
+        } else {
+            line.split(delimiter)
+        }
+    }
+
+    fn try_tokens(&self) -> TryTokens<'_> {
+        let start = self.pos.get();
+        let line = self.next_line();
+        TryTokens {
+            line,
+            start,
+            tokens: self.split(line, self.delimiter),
+        }
+    }
+
+    /// Returns the line at the current position and the position of the following line.
+    fn line_bounds(&self) -> (&[u8], usize) {
+        let pos = self.pos.get();
+        let (line, next) = if let Some(nline) = self.next_newline() {
+            (&self.data()[pos..nline], nline + 1)
+        } else {
+            (&self.data()[pos..], self.data().len())
+        };
+        if self.crlf {
+            (FastInput::strip_cr(line), next)
+        } else {
+            (line, next)
+        }
+    }
+
+    fn strip_bom(data: &[u8]) -> &[u8] {
+        &data[FastInput::bom_len(data)..]
+    }
+
+    /// Returns the length of the byte order mark at the start of `data`, if any.
+    fn bom_len(data: &[u8]) -> usize {
+        if data.starts_with(BOM) {
+            BOM.len()
+        } else {
+            0
+        }
+    }
+
+    /// Moves the position, forgetting the line that `unread_line` would go back to.
+    fn set_pos(&self, pos: usize) {
+        self.line_start.set(None);
+        self.pos.set(pos);
+    }
+
+    /// Checks that `pos` is not inside a UTF-8 character, like [`str::is_char_boundary`].
+    /// Positions at or past the end of the buffered data count as boundaries.
+    #[allow(clippy::unnecessary_map_or)]
+    fn is_char_boundary(&self, pos: usize) -> bool {
+        // Continuation bytes are 0b10xx_xxxx, which are below -0x40 as `i8`.
+        self.data().get(pos).map_or(true, |&b| (b as i8) >= -0x40)
+    }
+
+    fn strip_cr(line: &[u8]) -> &[u8] {
+        match line.last() {
+            Some(b'\r') => &line[..line.len() - 1],
+            _ => line,
+        }
+    }
+
+    fn next_newline(&self) -> Option<usize> {
+        self.next_byte(self.line_terminator)
+    }
+
+    /// Finds the next occurrence of `needle` at or after the position, reading more data if needed.
+    fn next_byte(&self, needle: u8) -> Option<usize> {
+        let mut from = self.pos.get();
+        loop {
+            if let Some(i) = memchr(needle, &self.data()[from..]) {
+                return Some(from + i);
+            }
+            from = self.data().len();
+            if !self.fill() {
+                return None;
+            }
+        }
+    }
+
+    /// Returns a (consuming) iterator over all remaining lines.
+    ///
+    /// Every `\n` ends a line, and a line without a trailing `\n` ends at EOF.
+    /// A `\n` at the very end of the input therefore does not produce an extra
+    /// empty line, while blank lines within the input are returned as empty strings:
+    /// `"a\nb\n"` yields `["a", "b"]` and `"a\n\nb"` yields `["a", "", "b"]`.
+    ///
+    /// # Examples
+    ///
+    /// Printing all lines:
+    /// ```rust
+    /// use fast_input::FastInput;
+    ///
+    /// let data = "First\nSecond\nThird".as_bytes();
+    /// let input = FastInput::with_reader(data);
+    /// let all_lines: Vec<_> = input.lines().collect();
+    ///
+    /// assert_eq!(&all_lines, &["First", "Second", "Third"]);
+    /// assert_eq!(input.has_next_line(), false);
+    /// ```
+    ///
+    /// `&FastInput` also implements `IntoIterator`, iterating over the lines:
+    /// ```rust
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("First\nSecond".as_bytes());
+    /// for line in &input {
+    ///     println!("{}", line);
+    /// }
+    /// ```
+    pub fn lines(&self) -> Lines<'_> {
+        Lines { input: self }
+    }
+
+    /// Returns a (consuming) iterator over all remaining lines, paired with
+    /// their 1-based line numbers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("header\na\nb\n".as_bytes());
+    /// input.next_line();
+    /// let lines: Vec<_> = input.enumerated_lines().collect();
+    /// assert_eq!(vec![(2, "a"), (3, "b")], lines);
+    /// ```
+    pub fn enumerated_lines(&self) -> impl Iterator<Item = (usize, &str)> {
+        let first = self.current_line_number();
+        self.lines().enumerate().map(move |(i, line)| (first + i, line))
+    }
+
+    /// Returns a (consuming) iterator over all remaining lines as owned
+    /// [`String`]s, which drops the consumed input in streaming mode.
+    ///
+    /// As the lines are owned, no borrows of the input are alive while iterating,
+    /// so in streaming mode (see [`Self::streaming_from`]) the data already read is
+    /// dropped as the iteration proceeds, keeping memory usage bounded by
+    /// the line length rather than the input size. Dropping data moves the
+    /// start of the input, so positions (see [`Self::position`]) and checkpoints
+    /// saved before the iteration are invalidated.
+    ///
+    /// Outside of streaming mode, this is the same as [`Self::owned_lines`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fast_input::FastInput;
+    ///
+    /// let mut input = FastInput::streaming_from(std::io::Cursor::new("a\nb\n"));
+    /// let lines: Vec<String> = input.drain_lines().collect();
+    /// assert_eq!(vec!["a", "b"], lines);
+    /// ```
+    pub fn drain_lines(&mut self) -> DrainLines<'_> {
+        DrainLines { input: self }
+    }
+
+    /// Returns a (consuming) iterator over all remaining lines as owned [`String`]s.
+    ///
+    /// # Examples
+    ///
+    /// Storing lines while continuing to use the input:
+    /// ```rust
+    /// use fast_input::FastInput;
+    ///
+    /// struct Document {
+    ///     lines: Vec<String>,
+    /// }
+    ///
+    /// let input = FastInput::with_reader("First\nSecond".as_bytes());
+    /// let doc = Document { lines: input.owned_lines().collect() };
+    /// input.reset();
+    ///
+    /// assert_eq!(doc.lines, ["First", "Second"]);
+    /// ```
+    pub fn owned_lines(&self) -> impl Iterator<Item = String> + '_ {
+        self.lines().map(str::to_owned)
+    }
+
+    /// Returns a (consuming) iterator over all remaining whitespace separated
+    /// tokens, parsed, regardless of the line structure.
+    ///
+    /// # Examples
+    ///
+    /// Reading all numbers in the input:
+    /// ```rust
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("  1 2\n\n3\t4  \n".as_bytes());
+    /// let all: Vec<i64> = input.tokens().collect();
+    /// assert_eq!(vec![1, 2, 3, 4], all);
+    /// ```
+    pub fn tokens<'a, T: FParse<'a>>(&'a self) -> impl Iterator<Item = T> + 'a {
+        std::iter::from_fn(move || self.try_next_token())
+    }
+
+    /// Returns a (consuming) iterator reading pairs of whitespace separated
+    /// tokens, regardless of the line structure.
+    ///
+    /// The iterator ends at the end of the input. A lone trailing token, which
+    /// has no partner, is consumed but not returned.
+    ///
+    /// # Examples
+    ///
+    /// Reading an edge list given as a flat stream:
+    /// ```rust
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1 2 2\n3 3 1\n".as_bytes());
+    /// let edges: Vec<(u32, u32)> = input.all_pairs().collect();
+    /// assert_eq!(vec![(1, 2), (2, 3), (3, 1)], edges);
+    /// ```
+    pub fn all_pairs<'a, T1, T2>(&'a self) -> impl Iterator<Item = (T1, T2)> + 'a
+    where
+        T1: FParse<'a>,
+        T2: FParse<'a>,
+    {
+        std::iter::from_fn(move || {
+            let first = self.try_next_token()?;
+            let second = self.try_next_token()?;
+            Some((first, second))
+        })
+    }
+
+    /// Returns a (consuming) iterator reading each remaining line as a tuple,
+    /// see [`FastParse`]. The iterator ends once only blank lines remain.
+    ///
+    /// # Examples
+    ///
+    /// Reading an edge list:
+    /// ```rust
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1 2\n2 3\n3 1\n".as_bytes());
+    /// let edges: Vec<(u32, u32)> = input.tuples().collect();
+    ///
+    /// assert_eq!(vec![(1, 2), (2, 3), (3, 1)], edges);
+    /// ```
+    pub fn tuples<'a, T>(&'a self) -> impl Iterator<Item = T> + 'a
+    where
+        FastInput: FastParse<'a, T>,
+    {
+        (0..).take_while(move |_| self.has_next_nonempty_line())
+            .map(move |_| self.next())
+    }
+
+    /// Returns a (consuming) iterator reading the remaining lines as
+    /// alternating keys and values, see [`Self::next_kv_lines`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("width\n80\nheight\n24\n".as_bytes());
+    /// let settings: Vec<(String, u32)> = input.kv_lines().collect();
+    /// assert_eq!(vec![("width".to_string(), 80), ("height".to_string(), 24)], settings);
+    /// ```
+    /// # Panics
+    /// If a line can not be parsed, or if the number of remaining lines is odd.
+    pub fn kv_lines<K, V>(&self) -> impl Iterator<Item = (K, V)> + '_
+    where
+        K: FromStr,
+        V: FromStr,
+        <K as FromStr>::Err: std::fmt::Debug,
+        <V as FromStr>::Err: std::fmt::Debug,
+    {
+        (0..).take_while(move |_| self.has_next_line())
+            .map(move |_| self.next_kv_lines())
+    }
+
+    /// Returns a (consuming) iterator reading each line as a tuple, see
+    /// [`FastParse`], until a line equal to `sentinel` is read.
+    ///
+    /// The sentinel line is consumed but not yielded. The iterator also
+    /// ends if the input ends before the sentinel is found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1 2\n3 4\n0 0\n5 6\n".as_bytes());
+    /// let pairs: Vec<(u32, u32)> = input.take_until((0, 0)).collect();
+    ///
+    /// assert_eq!(vec![(1, 2), (3, 4)], pairs);
+    /// assert_eq!("5 6", input.next_line());
+    /// ```
+    pub fn take_until<'a, T>(&'a self, sentinel: T) -> impl Iterator<Item = T> + 'a
+    where
+        FastInput: FastParse<'a, T>,
+        T: PartialEq + 'a,
+    {
+        self.tuples().take_while(move |t| *t != sentinel)
+    }
+
+    /// Reads a count `n` from the next line and returns an iterator reading the
+    /// following `n` lines as tuples, see [`FastParse`].
+    ///
+    /// The count is read when `read_n` is called, the tuples are read lazily.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("2\n1 2\n2 3\n4 5 6".as_bytes());
+    /// let edges: Vec<(u32, u32)> = input.read_n().collect();
+    ///
+    /// assert_eq!(vec![(1, 2), (2, 3)], edges);
+    /// assert_eq!("4 5 6", input.next_line());
+    /// ```
+    /// # Panics
+    /// If the count line is missing or is not a valid `usize`, or if there
+    /// are fewer than `n` lines following it.
+    pub fn read_n<'a, T>(&'a self) -> impl Iterator<Item = T> + 'a
+    where
+        FastInput: FastParse<'a, T>,
+    {
+        let n: usize = self.next_line_as();
+        (0..n).map(move |_| self.next())
+    }
+}
+
+const WORD_SIZE: usize = std::mem::size_of::<usize>();
+const LO_BYTES: usize = usize::MAX / 255;
+const HI_BYTES: usize = LO_BYTES << 7;
+
+/// Returns the index of the first occurrence of `needle` in `haystack`.
+///
+/// Scans a word at a time, using the classic "has zero byte" bit trick
+/// to skip words that can not contain `needle`.
+fn memchr(needle: u8, haystack: &[u8]) -> Option<usize> {
+    // Short lines are common, check the first bytes one at a time.
+    let head = haystack.len().min(2 * WORD_SIZE);
+    if let Some(i) = haystack[..head].iter().position(|&b| b == needle) {
+        return Some(i);
+    }
+    let repeated = LO_BYTES * needle as usize;
+    let mut offset = head;
+    for chunk in haystack[head..].chunks_exact(WORD_SIZE) {
+        let word = usize::from_ne_bytes(chunk.try_into().unwrap()) ^ repeated;
+        if word.wrapping_sub(LO_BYTES) & !word & HI_BYTES != 0 {
+            break;
+        }
+        offset += WORD_SIZE;
+    }
+    haystack[offset..]
+        .iter()
+        .position(|&b| b == needle)
+        .map(|i| offset + i)
+}
+
+/// Iterator over the remaining lines of a [`FastInput`], see [`FastInput::lines`].
+pub struct Lines<'a> {
+    input: &'a FastInput,
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.input.try_next_line()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.lines_size_hint()
+    }
+}
+
+impl FusedIterator for Lines<'_> {}
+
+impl<'a> IntoIterator for &'a FastInput {
+    type Item = &'a str;
+    type IntoIter = Lines<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.lines()
+    }
+}
+
+/// Iterator over the remaining lines of a [`FastInput`] as owned `String`s,
+/// see [`FastInput::drain_lines`].
+pub struct DrainLines<'a> {
+    input: &'a mut FastInput,
+}
+
+impl Iterator for DrainLines<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.input.compact();
+        self.input.try_next_line().map(str::to_owned)
+    }
+}
+
+impl FusedIterator for DrainLines<'_> {}
+
+/// Skips a UTF-8 byte order mark at the start of a streaming source.
+struct SkipBom<R> {
+    inner: R,
+    checked: bool,
+    /// Bytes read while matching a BOM split across reads that turned out
+    /// not to be one, returned before any further data of `inner`.
+    pending: Vec<u8>,
+}
+
+impl<R: BufRead> SkipBom<R> {
+    fn new(inner: R) -> Self {
+        SkipBom { inner, checked: false, pending: Vec::new() }
+    }
+
+    /// Reads until the input is known to start with a BOM or not, dropping it if so.
+    fn check(&mut self) -> std::io::Result<()> {
+        while self.pending.len() < BOM.len() {
+            let buf = self.inner.fill_buf()?;
+            if buf.is_empty() {
+                break;
+            }
+            let wanted = &BOM[self.pending.len()..];
+            let n = buf.iter().zip(wanted).take_while(|(a, b)| a == b).count();
+            let mismatch = n < wanted.len().min(buf.len());
+            self.pending.extend_from_slice(&buf[..n]);
+            self.inner.consume(n);
+            if mismatch {
+                break;
+            }
+        }
+        if self.pending == BOM {
+            self.pending.clear();
+        }
+        self.checked = true;
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Read for SkipBom<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let data = self.fill_buf()?;
+        let n = data.len().min(buf.len());
+        buf[..n].copy_from_slice(&data[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for SkipBom<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if !self.checked {
+            self.check()?;
+        }
+        if self.pending.is_empty() {
+            self.inner.fill_buf()
+        } else {
+            Ok(&self.pending)
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if self.pending.is_empty() {
+            self.inner.consume(amt)
+        } else {
+            self.pending.drain(..amt);
+        }
+    }
+}
+
+/// Reads tokens from a single line of a [`FastInput`], see [`FastInput::line_scanner`].
+///
+/// Tokens are separated by spaces and tabs, independent of the configured
+/// delimiter, and the scanner never reads past the end of the line.
+pub struct LineScanner<'a> {
+    input: &'a FastInput,
+}
+
+impl<'a> LineScanner<'a> {
+    /// Reads the next token on the line and parses it.
+    ///
+    /// # Panics
+    /// If there are no more tokens on the line.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<T: FParse<'a>>(&mut self) -> T {
+        self.try_next().expect("no more tokens on line")
+    }
+
+    /// Reads the next token on the line and parses it, or returns `None`
+    /// if there are no more tokens on the line.
+    pub fn try_next<T: FParse<'a>>(&mut self) -> Option<T> {
+        let (start, end) = self.input.line_token_bounds()?;
+        self.input.set_pos(end);
+        unsafe { Some(T::fparse(from_utf8_unchecked(&self.input.data()[start..end]))) }
+    }
+
+    /// Reads the next token on the line and parses it, or returns `default`
+    /// if there are no more tokens on the line.
+    ///
+    /// # Examples
+    ///
+    /// Reading an optional trailing field:
+    /// ```
+    /// use fast_input::FastInput;
+    ///
+    /// let input = FastInput::with_reader("1 5\n2\n".as_bytes());
+    /// let mut weights = Vec::new();
+    /// while input.has_next_line() {
+    ///     let mut scanner = input.line_scanner();
+    ///     let node: u32 = scanner.next();
+    ///     weights.push((node, scanner.next_or(1)));
+    ///     input.finish_line();
+    /// }
+    /// assert_eq!(vec![(1, 5), (2, 1)], weights);
+    /// ```
+    /// # Panics
+    /// If the token is present but can not be parsed.
+    pub fn next_or<T: FParse<'a>>(&mut self, default: T) -> T {
+        self.try_next().unwrap_or(default)
+    }
+
+    /// Checks if there are any more tokens on the line.
+    pub fn has_next(&self) -> bool {
+        self.input.line_token_bounds().is_some()
+    }
+}
+
+/// A cursor over the buffer of a [`FastInput`], see [`FastInput::fork`].
+///
+/// Provides the basic line and token readers of `FastInput`, with the same semantics.
+pub struct FastInputView<'a> {
+    data: &'a [u8],
+    pos: Cell<usize>,
+    crlf: bool,
+    line_terminator: u8,
+}
+
+impl<'a> FastInputView<'a> {
+    /// Reads the next line and returns it, see [`FastInput::next_line`].
+    pub fn next_line(&self) -> &'a str {
+        let pos = self.pos.get();
+        let rest = &self.data[pos..];
+        let (line, next) = match memchr(self.line_terminator, rest) {
+            Some(i) => (&rest[..i], pos + i + 1),
+            None => (rest, self.data.len()),
+        };
+        self.pos.set(next);
+        let line = if self.crlf { FastInput::strip_cr(line) } else { line };
+        unsafe { from_utf8_unchecked(line) }
+    }
+
+    /// Reads the next line and returns it, or `None` if there is no more data.
+    pub fn try_next_line(&self) -> Option<&'a str> {
+        if self.has_next_line() {
+            Some(self.next_line())
+        } else {
+            None
+        }
+    }
+
+    /// Checks if there is more data available in the buffer.
+    pub fn has_next_line(&self) -> bool {
+        self.pos.get() != self.data.len()
+    }
+
+    /// Returns a (consuming) iterator over all remaining lines.
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> + '_ {
+        std::iter::from_fn(move || self.try_next_line())
+    }
+
+    /// Reads the next whitespace separated token and parses it, see [`FastInput::next_token`].
+    ///
+    /// # Panics
+    /// If there are no more tokens in the buffer.
+    pub fn next_token<T: FParse<'a>>(&self) -> T {
+        self.try_next_token().expect("no more tokens in input")
+    }
+
+    /// Reads the next whitespace separated token and parses it, or returns
+    /// `None` if there are no more tokens.
+    pub fn try_next_token<T: FParse<'a>>(&self) -> Option<T> {
+        let rest = &self.data[self.pos.get()..];
+        let start = rest.iter().position(|b| !b.is_ascii_whitespace())?;
+        let len = rest[start..]
+            .iter()
+            .position(u8::is_ascii_whitespace)
+            .unwrap_or(rest.len() - start);
+        self.pos.set(self.pos.get() + start + len);
+        unsafe { Some(T::fparse(from_utf8_unchecked(&rest[start..start + len]))) }
+    }
+
+    /// Checks if there are any more (non-whitespace) tokens in the buffer.
+    pub fn has_next_token(&self) -> bool {
+        self.data[self.pos.get()..].iter().any(|b| !b.is_ascii_whitespace())
+    }
+
+    /// Returns the current position, as a byte offset from the start of the input.
+    pub fn position(&self) -> usize {
+        self.pos.get()
+    }
+
+    /// Moves the position back to the start of the buffer.
+    pub fn reset(&self) {
+        self.pos.set(0);
+    }
+}
+
+/// Kind of a token, see [`FastInput::peek_token_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// Digits, optionally preceded by a `-` or `+` sign, e.g. `-12`.
+    Integer,
+    /// A decimal number with a fraction or an exponent, e.g. `3.5` or `1e9`.
+    /// `inf` and `NaN` are words.
+    Float,
+    /// Any other token.
+    Word,
+    /// There are no more tokens.
+    Empty,
+}
+
+impl TokenKind {
+    fn of(token: &[u8]) -> TokenKind {
+        let digits = match token.first() {
+            Some(b'-') | Some(b'+') => &token[1..],
+            _ => token,
+        };
+        if !digits.is_empty() && digits.iter().all(u8::is_ascii_digit) {
+            return TokenKind::Integer;
+        }
+        let is_float = token.iter().any(u8::is_ascii_digit)
+            && std::str::from_utf8(token).is_ok_and(|t| t.parse::<f64>().is_ok());
+        if is_float {
+            TokenKind::Float
+        } else {
+            TokenKind::Word
+        }
+    }
+}
+
+/// A saved position in a [`FastInput`], see [`FastInput::checkpoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    pos: usize,
+}
+
+/// Number of remaining bytes shown by the `Debug` implementation of [`FastInput`].
+const DEBUG_PREVIEW_LEN: usize = 40;
+
+impl std::fmt::Debug for FastInput {
+    /// Shows the position, the length of the input and a preview of the remaining input.
+    ///
+    /// In streaming mode, only the input read so far is shown.
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rest = &self.data()[self.pos.get()..];
+        let preview = &rest[..rest.len().min(DEBUG_PREVIEW_LEN)];
+        let ellipsis = if preview.len() < rest.len() { "..." } else { "" };
+        fmt.debug_struct("FastInput")
+            .field("position", &self.pos.get())
+            .field("len", &self.data().len())
+            .field(
+                "remaining",
+                &format_args!("\"{}\"{}", preview.escape_ascii(), ellipsis),
+            )
+            .finish()
+    }
+}
+
+impl Clone for FastInput {
+    /// Copies the input, the current position and the configuration.
+    ///
+    /// In streaming mode, the rest of the input is read first, and a memory
+    /// mapped input is copied into memory.
+    fn clone(&self) -> Self {
+        while self.fill() {}
+        FastInput {
+            data: UnsafeCell::new(self.data().to_vec()),
+            start: 0,
+            pos: Cell::new(self.pos.get()),
+            delimiter: self.delimiter,
+            trim: self.trim,
+            crlf: self.crlf,
+            line_terminator: self.line_terminator,
+            null_token: self.null_token.clone(),
+            group_separator: self.group_separator,
+            max_line_len: self.max_line_len.clone(),
+            line_start: self.line_start.clone(),
+            source: RefCell::new(None),
+            retired: RefCell::new(Vec::new()),
+            #[cfg(all(feature = "mmap", unix, target_pointer_width = "64"))]
+            mmap: None,
+        }
+    }
+}
+
+impl Default for FastInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Declares variables read from whitespace separated tokens of a [`FastInput`].
+///
+/// Accepts an input expression followed by a comma separated list of
+/// `name: Type` pairs, where each name may be prefixed by `mut`:
+///
+/// ```text
+/// read!(input, [mut] name: Type, [mut] name: Type, ...);
+/// ```
+///
+/// Each pair expands to `let name: Type = input.next_token();`, so the
+/// tokens are read in order, see [`FastInput::next_token`]. `Type` can be
+/// any type implementing [`FParse`], including [`Str`]. The input
+/// expression is evaluated once for each variable.
+///
+/// # Examples
+///
+/// ```
+/// use fast_input::{read, FastInput, Str};
+///
+/// let input = FastInput::with_reader("3 2.5 Ada\n".as_bytes());
+/// read!(input, mut count: u32, weight: f64, name: Str);
+/// count += 1;
+/// assert_eq!((4, 2.5), (count, weight));
+/// assert!(name == "Ada");
+/// ```
+/// # Panics
+/// If there are not enough tokens, or if a token can not be parsed.
+#[macro_export]
+macro_rules! read {
+    ($input:expr $(,)?) => {};
+    ($input:expr, mut $name:ident : $t:ty $(, $($rest:tt)*)?) => {
+        let mut $name: $t = $input.next_token();
+        $crate::read!($input $(, $($rest)*)?);
+    };
+    ($input:expr, $name:ident : $t:ty $(, $($rest:tt)*)?) => {
+        let $name: $t = $input.next_token();
+        $crate::read!($input $(, $($rest)*)?);
+    };
+}
+
+/// Reads a line of elements separated by the delimiter (a space by default)
+/// and returns them parsed as a tuple.
+///
+/// Implemented for tuples of two up to twelve elements, where each element
+/// implements [`FParse`]. `next` always consumes the whole line, and extra
+/// elements on the line are ignored. To read a single value, see
+/// [`FastInput::next_token`] or [`FastInput::next_line_as`].
+///
+/// # Examples
+///
+/// Reading an `i32` and a `f64`:
+/// ```no_run
+/// use fast_input::{FastInput, FastParse};
+///
+/// let input = FastInput::new();
+/// let (age, length): (i32, f64) = input.next();
+/// println!("{} {}", age, length);
+/// ```
+/// # Panics
+/// If there is no more data in the buffer (see [`FastInput::has_next_line`]),
+/// or if the line has fewer elements than the tuple.
+pub trait FastParse<'a, T> {
+    fn next(&'a self) -> T;
+}
+
+/// Fallible version of [`FastParse`], returns a [`ParseError`] if an element
+/// is missing or can not be parsed. The elements are parsed with [`TryFParse`].
+///
+/// # Examples
+///
+/// ```
+/// use fast_input::{FastInput, ParseError, TryFastParse};
+///
+/// let input = FastInput::with_reader("12 abc\n12".as_bytes());
+/// let first: Result<(i32, i32), _> = input.try_next();
+/// let second: Result<(i32, i32), _> = input.try_next();
+///
+/// assert_eq!(Err(ParseError::InvalidToken {
+///     token: "abc".to_owned(),
+///     expected: "i32",
+///     position: 3,
+/// }), first);
+/// assert_eq!(Err(ParseError::MissingToken { position: 9 }), second);
+/// ```
+pub trait TryFastParse<'a, T> {
+    fn try_next(&'a self) -> Result<T, ParseError>;
+}
+
+/// Reads a line of elements separated by a sequence of separators, and returns
+/// them parsed as a tuple.
+///
+/// The line is split at the first occurrence of each separator in turn, so
+/// `seps` must contain one separator less than the number of elements.
+/// The line is trimmed first, unless disabled with [`FastInput::with_trim`].
+/// Implemented for tuples of two up to twelve elements.
+///
+/// # Examples
+///
+/// Reading a date and time:
+/// ```
+/// use fast_input::{FastInput, FastParseSeps};
+///
+/// let input = FastInput::with_reader("2024-02-29 12:30".as_bytes());
+/// let date: (u16, u8, u8, u8, u8) = input.next_tuple_seps(&['-', '-', ' ', ':']);
+/// assert_eq!((2024, 2, 29, 12, 30), date);
+/// ```
+/// # Panics
+/// If there is no more data in the buffer (see [`FastInput::has_next_line`]),
+/// if the number of separators does not match the tuple, or if a separator is
+/// not found in the line.
+pub trait FastParseSeps<'a, T> {
+    fn next_tuple_seps(&'a self, seps: &[char]) -> T;
+}
+
+/// Splits a line at the first occurrence of each separator in turn.
+struct SeparatedParts<'a, 'b> {
+    line: &'a str,
+    rest: Option<&'a str>,
+    seps: std::slice::Iter<'b, char>,
+}
+
+impl<'a, 'b> SeparatedParts<'a, 'b> {
+    fn new(line: &'a str, seps: &'b [char], len: usize) -> Self {
+        assert!(
+            seps.len() + 1 == len,
+            "expected {} separators for {} elements, got {}",
+            len - 1,
+            len,
+            seps.len()
+        );
+        SeparatedParts { line, rest: Some(line), seps: seps.iter() }
+    }
+}
+
+impl<'a, 'b> Iterator for SeparatedParts<'a, 'b> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let rest = self.rest?;
+        match self.seps.next() {
+            Some(&sep) => {
+                let (part, tail) = rest.split_once(sep).unwrap_or_else(|| {
+                    panic!("separator {:?} not found in line {:?}", sep, self.line)
+                });
+                self.rest = Some(tail);
+                Some(part)
+            }
+            None => self.rest.take(),
+        }
+    }
+}
+
+macro_rules! impl_fast_parse_tuple {
+    ($($t:ident),+) => {
+        impl<'a, $($t),+> FastParse<'a, ($($t,)+)> for FastInput
+        where
+            $($t: FParse<'a>),+
+        {
+            fn next(&'a self) -> ($($t,)+) {
+                const LEN: usize = [$(stringify!($t)),+].len();
+                let mut it = self.next_split();
+                ($($t::fparse(it.next().unwrap_or_else(|| {
+                    panic!("expected {} elements on line", LEN)
+                })),)+)
+            }
+        }
+
+        impl<'a, $($t),+> FastParseSeps<'a, ($($t,)+)> for FastInput
+        where
+            $($t: FParse<'a>),+
+        {
+            fn next_tuple_seps(&'a self, seps: &[char]) -> ($($t,)+) {
+                const LEN: usize = [$(stringify!($t)),+].len();
+                let line = self.next_line();
+                let line = if self.trim { line.trim() } else { line };
+                let mut it = SeparatedParts::new(line, seps, LEN);
+                ($($t::fparse(it.next().unwrap()),)+)
+            }
+        }
+
+        impl<'a, $($t),+> TryFastParse<'a, ($($t,)+)> for FastInput
+        where
+            $($t: TryFParse<'a>),+
+        {
+            fn try_next(&'a self) -> Result<($($t,)+), ParseError> {
+                let mut it = self.try_tokens();
+                Ok(($(it.next::<$t>()?,)+))
+            }
+        }
+    };
+}
+
+impl_fast_parse_tuple!(T1, T2);
+impl_fast_parse_tuple!(T1, T2, T3);
+impl_fast_parse_tuple!(T1, T2, T3, T4);
+impl_fast_parse_tuple!(T1, T2, T3, T4, T5);
+impl_fast_parse_tuple!(T1, T2, T3, T4, T5, T6);
+impl_fast_parse_tuple!(T1, T2, T3, T4, T5, T6, T7);
+impl_fast_parse_tuple!(T1, T2, T3, T4, T5, T6, T7, T8);
+impl_fast_parse_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_fast_parse_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_fast_parse_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_fast_parse_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+
+/// Error returned by the fallible (`try_`) reading methods.
+///
+/// Positions are byte offsets into the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The line ended before all expected tokens were read, or a token
+    /// was empty (e.g. two consecutive spaces).
+    /// `position` is the offset where the token was expected.
+    MissingToken { position: usize },
+    /// A token could not be parsed into the expected type.
+    InvalidToken {
+        token: String,
+        expected: &'static str,
+        position: usize,
+    },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::MissingToken { position } => {
+                write!(fmt, "missing token at byte {}", position)
+            }
+            ParseError::InvalidToken { token, expected, position } => write!(
+                fmt,
+                "failed to parse {:?} as {} at byte {}",
+                token, expected, position
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Fallible token reader over a single line, used by [`TryFastParse`].
+struct TryTokens<'a> {
+    line: &'a str,
+    /// Position of `line` in the input.
+    start: usize,
+    tokens: std::str::Split<'a, char>,
+}
+
+impl<'a> TryTokens<'a> {
+    fn next<T: TryFParse<'a>>(&mut self) -> Result<T, ParseError> {
+        let token = match self.tokens.next() {
+            Some(token) if !token.is_empty() => token,
+            Some(token) => {
+                let position = self.offset_of(token);
+                return Err(ParseError::MissingToken { position });
+            }
+            None => {
+                let position = self.start + self.line.len();
+                return Err(ParseError::MissingToken { position });
+            }
+        };
+        T::try_fparse(token).ok_or_else(|| ParseError::InvalidToken {
+            token: token.to_owned(),
+            expected: type_name::<T>(),
+            position: self.offset_of(token),
+        })
+    }
+
+    fn offset_of(&self, token: &str) -> usize {
+        self.start + (token.as_ptr() as usize - self.line.as_ptr() as usize)
+    }
+}
+
+/// Integer types that can be read with [`FastInput::next_int`].
+pub trait FastInt: Sized {
+    /// Parses ASCII `digits` into an integer, negated if `negative` is set.
+    /// Returns `None` if `digits` is empty, contains a non-digit, or if
+    /// the value does not fit in the type.
+    fn from_digits(digits: &[u8], negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_fast_int {
+    ($($t:ty),+) => {
+        $(
+            impl FastInt for $t {
+                fn from_digits(digits: &[u8], negative: bool) -> Option<Self> {
+                    if digits.is_empty() {
+                        return None;
+                    }
+                    let mut value: $t = 0;
+                    for &b in digits {
+                        let digit = b.wrapping_sub(b'0');
+                        if digit > 9 {
+                            return None;
+                        }
+                        value = value.checked_mul(10)?;
+                        // Accumulating negative values directly allows reading `MIN`.
+                        value = if negative {
+                            value.checked_sub(digit as $t)?
+                        } else {
+                            value.checked_add(digit as $t)?
+                        };
+                    }
+                    Some(value)
+                }
+            }
+        )+
+    };
+}
+
+impl_fast_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Floating point types that can be read with [`FastInput::next_float`].
+pub trait FastFloat: Sized {
+    /// Parses a float from its textual representation in `bytes`,
+    /// returns `None` if `bytes` is not a valid float.
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+/// Splits a plain decimal such as `-12.5e-3` into its sign, mantissa and
+/// base 10 exponent. Returns `None` if `bytes` is not a plain decimal, or if
+/// the mantissa has too many digits to fit in a `u64`.
+fn parse_decimal(bytes: &[u8]) -> Option<(bool, u64, i32)> {
+    let (negative, mut rest) = match bytes.first()? {
+        b'-' => (true, &bytes[1..]),
+        b'+' => (false, &bytes[1..]),
+        _ => (false, bytes),
+    };
+    let mut mantissa: u64 = 0;
+    let mut significant = 0;
+    let mut exponent: i32 = 0;
+    let mut any_digits = false;
+    let mut fraction = false;
+    while let Some((&b, tail)) = rest.split_first() {
+        match b {
+            b'0'..=b'9' => {
+                if mantissa != 0 || b != b'0' {
+                    significant += 1;
+                    if significant > 19 {
+                        return None;
+                    }
+                }
+                mantissa = mantissa * 10 + (b - b'0') as u64;
+                any_digits = true;
+                if fraction {
+                    exponent -= 1;
+                }
+            }
+            b'.' if !fraction => fraction = true,
+            _ => break,
+        }
+        rest = tail;
+    }
+    if !any_digits {
+        return None;
+    }
+    if let Some((b'e', tail)) | Some((b'E', tail)) = rest.split_first() {
+        let (negative_exp, digits) = match tail.first() {
+            Some(b'-') => (true, &tail[1..]),
+            Some(b'+') => (false, &tail[1..]),
+            _ => (false, tail),
+        };
+        if digits.is_empty() || digits.len() > 4 {
+            return None;
+        }
+        let value = i32::from_digits(digits, negative_exp)?;
+        exponent += value;
+        rest = &[];
+    }
+    if rest.is_empty() {
+        Some((negative, mantissa, exponent))
+    } else {
+        None
+    }
+}
+
+macro_rules! impl_fast_float {
+    ($($t:ty: $mantissa_bits:expr, $max_exp:expr),+) => {
+        $(
+            impl FastFloat for $t {
+                fn from_bytes(bytes: &[u8]) -> Option<Self> {
+                    // Both the mantissa and the power of ten are exact in the float
+                    // type, so a single multiplication or division rounds correctly.
+                    match parse_decimal(bytes) {
+                        Some((negative, mantissa, exponent))
+                            if mantissa <= 1 << $mantissa_bits && exponent.abs() <= $max_exp =>
+                        {
+                            let pow = POWERS_OF_TEN[exponent.unsigned_abs() as usize] as $t;
+                            let value = if exponent < 0 {
+                                mantissa as $t / pow
+                            } else {
+                                mantissa as $t * pow
+                            };
+                            Some(if negative { -value } else { value })
+                        }
+                        _ => std::str::from_utf8(bytes).ok()?.parse().ok(),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+const POWERS_OF_TEN: [f64; 23] = [
+    1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15,
+    1e16, 1e17, 1e18, 1e19, 1e20, 1e21, 1e22,
+];
+
+impl_fast_float!(f32: 24, 10, f64: 53, 22);
+
+/// Integer types that can be parsed in an arbitrary radix, see [`FastInput::next_radix`].
+///
+/// Mirrors the inherent `from_str_radix` functions of the integer types,
+/// which are not part of any standard trait, and adds the radix counterparts
+/// of the [`FParse`] methods, which also allow a `0x`, `0o` or `0b` prefix,
+/// after an optional sign, for radix 16, 8 and 2 respectively.
+///
+/// # Examples
+///
+/// ```
+/// use fast_input::FromStrRadix;
+///
+/// assert_eq!(255u8, u8::fparse_radix("0xff", 16));
+/// assert_eq!(Some(-5i32), i32::try_fparse_radix("-101", 2));
+/// assert_eq!(None, u32::try_fparse_radix("12", 2));
+/// ```
+pub trait FromStrRadix: Sized {
+    /// Parses an integer in the given `radix`, like the inherent `from_str_radix`.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError>;
+
+    /// Parses an integer in the given `radix` from a string slice.
+    ///
+    /// # Panics
+    /// If the slice is not a valid integer in the given radix, or if
+    /// `radix` is not in the range 2 to 36.
+    fn fparse_radix(s: &str, radix: u32) -> Self {
+        Self::from_str_radix(&strip_radix_prefix(s, radix), radix).unwrap_or_else(|e| {
+            panic!("invalid base {} integer {:?}: {}", radix, s, e)
+        })
+    }
+
+    /// Parses an integer in the given `radix` from a string slice, returns
+    /// `None` if the slice is not a valid integer in the given radix.
+    ///
+    /// # Panics
+    /// If `radix` is not in the range 2 to 36.
+    fn try_fparse_radix(s: &str, radix: u32) -> Option<Self> {
+        Self::from_str_radix(&strip_radix_prefix(s, radix), radix).ok()
+    }
+}
+
+macro_rules! impl_from_str_radix {
+    ($($t:ty),+) => {
+        $(
+            impl FromStrRadix for $t {
+                fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError> {
+                    <$t>::from_str_radix(s, radix)
+                }
+            }
+        )+
+    };
+}
+
+impl_from_str_radix!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Removes the `0x`, `0o` or `0b` prefix for `radix`, after an optional sign.
+fn strip_radix_prefix(s: &str, radix: u32) -> Cow<'_, str> {
+    let prefix = match radix {
+        16 => "0x",
+        8 => "0o",
+        2 => "0b",
+        _ => return Cow::Borrowed(s),
+    };
+    let (sign, rest) = match s.as_bytes().first() {
+        Some(b'-') | Some(b'+') => s.split_at(1),
+        _ => ("", s),
+    };
+    let digits = match rest.get(..prefix.len()) {
+        Some(p) if p.eq_ignore_ascii_case(prefix) => &rest[prefix.len()..],
+        _ => return Cow::Borrowed(s),
+    };
+    if digits.starts_with(&['+', '-'][..]) {
+        // A sign after the prefix is invalid, keep the prefix so that parsing fails.
+        Cow::Borrowed(s)
+    } else if sign == "-" {
+        Cow::Owned(format!("-{}", digits))
+    } else {
+        Cow::Borrowed(digits)
+    }
+}
+
+/// Helper trait for parsing.
+/// Mainly used to avoid repeating type constraints.
+pub trait FParse<'a>: Sized {
+    /// Parses a type from a string slice
+    ///
+    /// # Panics
+    /// If the slice is not a valid representation of the type. The panic
+    /// message includes the slice and the name of the type.
+    fn fparse(s: &'a str) -> Self;
+}
+
+/// Fallible counterpart of [`FParse`], used by [`TryFastParse`].
+pub trait TryFParse<'a>: Sized {
+    /// Parses a type from a string slice, returns `None` if the
+    /// slice is not a valid representation of the type.
+    fn try_fparse(s: &'a str) -> Option<Self>;
+}
+
+impl<'a, T: FromStr> FParse<'a> for T
+where
+    <T as FromStr>::Err: std::fmt::Debug,
+{
+    fn fparse(s: &'a str) -> Self {
+        s.parse().unwrap_or_else(|e| {
+            panic!("failed to parse {:?} as {}: {:?}", s, type_name::<T>(), e)
+        })
+    }
+}
+
+impl<'a, T: FromStr> TryFParse<'a> for T {
+    fn try_fparse(s: &'a str) -> Option<Self> {
+        s.parse().ok()
+    }
+}
+
+/// Allows reading of string slices (`&str`).
+/// The standard library does not provide a `FromStr` implementation
+/// for `&str`. The `Str` type newtypes `&str` and implements `FParse`,
+/// `Deref<Target = &str>`, `AsRef<str>` and `Borrow<str>`.
+///
+/// # Examples
+///
+/// Reading (name, age, city) triples using `Str` and `FastInput`:
 /// ```rust
 /// use fast_input::{FastInput, FastParse, Str};
 /// let data = "Jakub 26 Mora".as_bytes();
@@ -402,15 +3688,46 @@ where
 /// //To use any functions related to `&str`, dereference the `Str` into a `&str`
 /// let name: &str = *name;
 ///
+/// // Str can be compared directly against string slices
+/// assert!(city == "Mora");
 /// ```
+#[derive(PartialEq, Eq, Hash)]
 pub struct Str<'a>(&'a str);
 
+impl Str<'_> {
+    /// Checks if the slice equals `other`, ignoring ASCII case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::{FastInput, Str};
+    ///
+    /// let input = FastInput::with_reader("YES".as_bytes());
+    /// let answer: Str = input.next_token();
+    /// assert!(answer.eq_ignore_case("yes"));
+    /// ```
+    pub fn eq_ignore_case(&self, other: &str) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+
+    /// Returns the slice in lowercase as an owned `String`.
+    pub fn to_lowercase_owned(&self) -> String {
+        self.0.to_lowercase()
+    }
+}
+
 impl<'a> FParse<'a> for Str<'a> {
     fn fparse(s: &'a str) -> Self {
         Str::<'a>(s)
     }
 }
 
+impl<'a> TryFParse<'a> for Str<'a> {
+    fn try_fparse(s: &'a str) -> Option<Self> {
+        Some(Str::<'a>(s))
+    }
+}
+
 impl<'a> Deref for Str<'a> {
     type Target = &'a str;
     fn deref(&self) -> &Self::Target {
@@ -418,8 +3735,350 @@ impl<'a> Deref for Str<'a> {
     }
 }
 
+impl AsRef<str> for Str<'_> {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl Borrow<str> for Str<'_> {
+    fn borrow(&self) -> &str {
+        self.0
+    }
+}
+
+impl PartialEq<str> for Str<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Str<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
 impl Display for Str<'_> {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.0.fmt(fmt)
     }
 }
+
+/// Allows reading of booleans written as `0`/`1`, `true`/`false` or `yes`/`no`.
+/// The `FromStr` implementation for `bool` only accepts `true` and `false`.
+/// The `Bool` type newtypes `bool`, accepts all of the above forms
+/// (case-insensitively) and implements `FParse` and `Deref<Target = bool>`.
+///
+/// # Examples
+///
+/// ```rust
+/// use fast_input::{Bool, FastInput};
+/// let input = FastInput::with_reader("1 0 YES no True".as_bytes());
+/// let flags: Vec<bool> = input.next_as_iter::<Bool>().map(|b| *b).collect();
+/// assert_eq!(vec![true, false, true, false, true], flags);
+/// ```
+///
+/// # Panics
+/// Parsing panics if the token is not one of the accepted forms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bool(bool);
+
+const BOOL_FORMS: [(&str, bool); 6] = [
+    ("1", true),
+    ("0", false),
+    ("true", true),
+    ("false", false),
+    ("yes", true),
+    ("no", false),
+];
+
+impl<'a> FParse<'a> for Bool {
+    fn fparse(s: &'a str) -> Self {
+        Bool::try_fparse(s).unwrap_or_else(|| {
+            panic!("invalid boolean {:?}, expected one of 0, 1, true, false, yes, no", s)
+        })
+    }
+}
+
+impl<'a> TryFParse<'a> for Bool {
+    fn try_fparse(s: &'a str) -> Option<Self> {
+        BOOL_FORMS
+            .iter()
+            .find(|(form, _)| form.eq_ignore_ascii_case(s))
+            .map(|&(_, value)| Bool(value))
+    }
+}
+
+impl Deref for Bool {
+    type Target = bool;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for Bool {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(fmt)
+    }
+}
+
+/// Allows reading of single characters.
+/// The `Char` type newtypes `char`, implements `FParse` and `Deref<Target = char>`.
+/// Parsing takes the first `char` of the token, any further characters are ignored.
+///
+/// Note that a `char` is a Unicode scalar value, not a grapheme. Multi-byte
+/// UTF-8 characters such as `ö` are read correctly, but for a grapheme made up of
+/// several scalar values (e.g. `e` followed by a combining accent) only the
+/// first one is returned.
+///
+/// # Examples
+///
+/// Reading a row of a character grid:
+/// ```rust
+/// use fast_input::{Char, FastInput};
+/// let input = FastInput::with_reader("# . # ö".as_bytes());
+/// let row: Vec<char> = input.next_as_iter::<Char>().map(|c| *c).collect();
+/// assert_eq!(vec!['#', '.', '#', 'ö'], row);
+/// ```
+///
+/// # Panics
+/// Parsing panics if the token is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Char(char);
+
+impl<'a> FParse<'a> for Char {
+    fn fparse(s: &'a str) -> Self {
+        Char::try_fparse(s).expect("can not read a char from an empty token")
+    }
+}
+
+impl<'a> TryFParse<'a> for Char {
+    fn try_fparse(s: &'a str) -> Option<Self> {
+        s.chars().next().map(Char)
+    }
+}
+
+impl Deref for Char {
+    type Target = char;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for Char {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(fmt)
+    }
+}
+
+/// Allows reading of a token made up of a group of elements, such as `1,2,3`.
+/// The `Group` type newtypes arrays and tuples (of two up to four elements) of
+/// types implementing `FParse`, splits the token on `SEP` (a comma by default)
+/// and implements `FParse` and `Deref` to the array or tuple.
+///
+/// # Examples
+///
+/// Reading coordinates:
+/// ```rust
+/// use fast_input::{FastInput, Group};
+/// let input = FastInput::with_reader("1,2,3 4,5,6\n7;x".as_bytes());
+/// let points: Vec<[i32; 3]> = input.next_as_iter::<Group<[i32; 3]>>().map(|p| *p).collect();
+/// assert_eq!(vec![[1, 2, 3], [4, 5, 6]], points);
+///
+/// let pair: Group<(u8, char), ';'> = input.next_token();
+/// assert_eq!((7, 'x'), *pair);
+/// ```
+///
+/// # Panics
+/// Parsing panics if the token does not have exactly as many elements as the group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Group<T, const SEP: char = ','>(T);
+
+impl<T, const SEP: char> Group<T, SEP> {
+    /// Returns the parsed array or tuple.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, const SEP: char> Deref for Group<T, SEP> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Splits a group token, panicking if it does not have `len` elements.
+fn split_group(s: &str, len: usize, separator: char) -> std::str::Split<'_, char> {
+    let count = s.split(separator).count();
+    assert!(
+        count == len,
+        "expected {} elements separated by {:?} in token {:?}, found {}",
+        len,
+        separator,
+        s,
+        count
+    );
+    s.split(separator)
+}
+
+impl<'a, T: FParse<'a>, const N: usize, const SEP: char> FParse<'a> for Group<[T; N], SEP> {
+    fn fparse(s: &'a str) -> Self {
+        let mut parts = split_group(s, N, SEP);
+        Group(std::array::from_fn(|_| T::fparse(parts.next().unwrap())))
+    }
+}
+
+impl<'a, T: TryFParse<'a>, const N: usize, const SEP: char> TryFParse<'a> for Group<[T; N], SEP> {
+    fn try_fparse(s: &'a str) -> Option<Self> {
+        if s.split(SEP).count() != N {
+            return None;
+        }
+        let mut parts = s.split(SEP);
+        let parsed: [Option<T>; N] = std::array::from_fn(|_| T::try_fparse(parts.next().unwrap()));
+        if parsed.iter().any(Option::is_none) {
+            return None;
+        }
+        Some(Group(parsed.map(Option::unwrap)))
+    }
+}
+
+macro_rules! impl_fparse_group_tuple {
+    ($len:expr; $($t:ident),+) => {
+        impl<'a, $($t: FParse<'a>,)+ const SEP: char> FParse<'a> for Group<($($t,)+), SEP> {
+            fn fparse(s: &'a str) -> Self {
+                let mut parts = split_group(s, $len, SEP);
+                Group(($($t::fparse(parts.next().unwrap()),)+))
+            }
+        }
+
+        impl<'a, $($t: TryFParse<'a>,)+ const SEP: char> TryFParse<'a> for Group<($($t,)+), SEP> {
+            fn try_fparse(s: &'a str) -> Option<Self> {
+                if s.split(SEP).count() != $len {
+                    return None;
+                }
+                let mut parts = s.split(SEP);
+                Some(Group(($($t::try_fparse(parts.next()?)?,)+)))
+            }
+        }
+    };
+}
+
+impl_fparse_group_tuple!(2; T1, T2);
+impl_fparse_group_tuple!(3; T1, T2, T3);
+impl_fparse_group_tuple!(4; T1, T2, T3, T4);
+
+/// Allows reading of ratios written as `numerator/denominator`, such as `3/4`.
+/// The `Ratio` type newtypes `(i64, i64)`, implements `FParse` and
+/// `Deref<Target = (i64, i64)>`. The ratio is not reduced, and a zero
+/// denominator is allowed.
+///
+/// # Examples
+///
+/// ```rust
+/// use fast_input::{FastInput, Ratio};
+/// let input = FastInput::with_reader("3/4 -1/2".as_bytes());
+/// let ratios: Vec<(i64, i64)> = input.next_as_iter::<Ratio>().map(|r| *r).collect();
+/// assert_eq!(vec![(3, 4), (-1, 2)], ratios);
+/// ```
+///
+/// # Panics
+/// Parsing panics if the token does not contain a `/`, or if the numerator or
+/// denominator is not an integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio((i64, i64));
+
+impl<'a> FParse<'a> for Ratio {
+    fn fparse(s: &'a str) -> Self {
+        let (numerator, denominator) = s
+            .split_once('/')
+            .unwrap_or_else(|| panic!("missing '/' in ratio {:?}", s));
+        Ratio((i64::fparse(numerator), i64::fparse(denominator)))
+    }
+}
+
+impl<'a> TryFParse<'a> for Ratio {
+    fn try_fparse(s: &'a str) -> Option<Self> {
+        let (numerator, denominator) = s.split_once('/')?;
+        Some(Ratio((numerator.parse().ok()?, denominator.parse().ok()?)))
+    }
+}
+
+impl Deref for Ratio {
+    type Target = (i64, i64);
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for Ratio {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(fmt, "{}/{}", (self.0).0, (self.0).1)
+    }
+}
+
+/// Allows reading of numbers with group separators, such as `1,000,000`.
+/// The `Grouped` type newtypes any `FromStr` type, removes all `SEP` characters
+/// (a comma by default) from the token before parsing, and implements `FParse`
+/// and `Deref` to the parsed value. To use the separator configured on the
+/// input instead, see [`FastInput::next_grouped`].
+///
+/// # Examples
+///
+/// ```rust
+/// use fast_input::{FastInput, Grouped};
+/// let input = FastInput::with_reader("1,000,000 12_345".as_bytes());
+/// let population: Grouped<u64> = input.next_token();
+/// let amount: Grouped<i32, '_'> = input.next_token();
+/// assert_eq!((1_000_000, 12_345), (*population, *amount));
+/// ```
+///
+/// # Panics
+/// Parsing panics if the token without separators can not be parsed into `T`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Grouped<T, const SEP: char = ','>(T);
+
+impl<T, const SEP: char> Grouped<T, SEP> {
+    /// Returns the parsed value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<'a, T, const SEP: char> FParse<'a> for Grouped<T, SEP>
+where
+    T: FromStr,
+    <T as FromStr>::Err: std::fmt::Debug,
+{
+    fn fparse(s: &'a str) -> Self {
+        Grouped(T::fparse(&remove_group_separator(s, SEP)))
+    }
+}
+
+impl<'a, T: FromStr, const SEP: char> TryFParse<'a> for Grouped<T, SEP> {
+    fn try_fparse(s: &'a str) -> Option<Self> {
+        remove_group_separator(s, SEP).parse().ok().map(Grouped)
+    }
+}
+
+fn remove_group_separator(s: &str, separator: char) -> Cow<'_, str> {
+    if s.contains(separator) {
+        Cow::Owned(s.replace(separator, ""))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+impl<T, const SEP: char> Deref for Grouped<T, SEP> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Display, const SEP: char> Display for Grouped<T, SEP> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(fmt)
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt::Display;
+use std::io::prelude::*;
+use std::io::{stdout, BufWriter, StdoutLock};
+
+use crate::BUFFER_SIZE;
+
+/// Buffered output, the counterpart of [`FastInput`](crate::FastInput).
+///
+/// Every `println!` locks standard out and, when connected to a terminal or
+/// pipe, flushes it. For solutions printing one answer per query this is slow.
+/// `FastOutput` locks standard out once and buffers all output, which is
+/// written in large chunks and flushed when the `FastOutput` is dropped.
+///
+/// `FastOutput` implements [`Write`], so the `write!` and `writeln!`
+/// macros can be used as well.
+///
+/// # Examples
+///
+/// ```no_run
+/// use fast_input::{FastInput, FastOutput};
+///
+/// let input = FastInput::new();
+/// let mut output = FastOutput::new();
+/// for line in input.lines() {
+///     output.putln(line.len());
+/// }
+/// ```
+pub struct FastOutput<W: Write = StdoutLock<'static>> {
+    out: BufWriter<W>,
+}
+
+impl FastOutput {
+    /// Creates a new FastOutput writing to standard out.
+    ///
+    /// Standard out stays locked until the FastOutput is dropped.
+    pub fn new() -> Self {
+        FastOutput::with_writer(stdout().lock())
+    }
+}
+
+impl<W: Write> FastOutput<W> {
+    /// Creates a new FastOutput writing to the given writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fast_input::FastOutput;
+    ///
+    /// let mut output = FastOutput::with_writer(Vec::new());
+    /// output.put(1);
+    /// output.write_str(" + ");
+    /// output.putln(2.5);
+    /// assert_eq!(b"1 + 2.5\n", &output.into_inner()[..]);
+    /// ```
+    pub fn with_writer(out: W) -> Self {
+        FastOutput {
+            out: BufWriter::with_capacity(BUFFER_SIZE, out),
+        }
+    }
+
+    /// Writes a string as is, without a separator or newline.
+    pub fn write_str(&mut self, s: &str) {
+        self.out
+            .write_all(s.as_bytes())
+            .expect("failed to write output");
+    }
+
+    /// Writes a string followed by a newline.
+    pub fn writeln(&mut self, s: &str) {
+        self.write_str(s);
+        self.write_str("\n");
+    }
+
+    /// Writes a value using its `Display` implementation.
+    pub fn put<T: Display>(&mut self, value: T) {
+        write!(self.out, "{}", value).expect("failed to write output");
+    }
+
+    /// Writes a value using its `Display` implementation, followed by a newline.
+    pub fn putln<T: Display>(&mut self, value: T) {
+        writeln!(self.out, "{}", value).expect("failed to write output");
+    }
+
+    /// Flushes the buffered output and returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.out
+            .into_inner()
+            .unwrap_or_else(|_| panic!("failed to flush output"))
+    }
+}
+
+impl Default for FastOutput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Write> Write for FastOutput<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.out.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.out.flush()
+    }
+}
@@ -0,0 +1,159 @@
+use std::io::prelude::*;
+use std::io::stdin;
+use std::str::from_utf8_unchecked;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{memchr, FParse, FastInput, BUFFER_SIZE};
+
+/// A thread safe variant of [`FastInput`].
+///
+/// `FastInput` keeps its position in a `Cell`, which makes it `!Sync`.
+/// `SyncFastInput` keeps the position in an `AtomicUsize` instead, so a
+/// `&SyncFastInput` can be shared between threads. Every read claims its
+/// line (or token) with an atomic compare-and-swap, so each line is returned
+/// to exactly one reader, but the order in which threads receive lines is
+/// unspecified.
+///
+/// # Examples
+///
+/// Summing lines on several threads:
+/// ```
+/// use fast_input::SyncFastInput;
+/// use std::sync::atomic::{AtomicU64, Ordering};
+///
+/// let input = SyncFastInput::with_reader("1\n2\n3\n4\n5".as_bytes());
+/// let sum = AtomicU64::new(0);
+/// std::thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| {
+///             while let Some(line) = input.try_next_line() {
+///                 sum.fetch_add(line.parse::<u64>().unwrap(), Ordering::Relaxed);
+///             }
+///         });
+///     }
+/// });
+/// assert_eq!(15, sum.into_inner());
+/// ```
+pub struct SyncFastInput {
+    data: Vec<u8>,
+    pos: AtomicUsize,
+}
+
+impl SyncFastInput {
+    /// Creates a new SyncFastInput, reading all of standard input.
+    ///
+    /// For more information, see [`FastInput::new`].
+    pub fn new() -> Self {
+        SyncFastInput::with_reader(stdin().lock())
+    }
+
+    /// Creates a new SyncFastInput with a given input that implements `Read`.
+    ///
+    /// A UTF-8 byte order mark at the start of the input is skipped.
+    pub fn with_reader<T: Read>(input: T) -> Self {
+        let data = FastInput::read_to_end(input, BUFFER_SIZE);
+        SyncFastInput {
+            pos: AtomicUsize::new(FastInput::bom_len(&data)),
+            data,
+        }
+    }
+
+    /// Reads the next line and returns it, or `None` if there is no more data.
+    ///
+    /// For more information, see [`FastInput::next_line`].
+    pub fn try_next_line(&self) -> Option<&str> {
+        let (start, end) = self.claim(|pos| {
+            if pos == self.data.len() {
+                return None;
+            }
+            Some(match memchr(b'\n', &self.data[pos..]) {
+                Some(i) => (pos + i, pos + i + 1),
+                None => (self.data.len(), self.data.len()),
+            })
+        })?;
+        unsafe {
+            Some(from_utf8_unchecked(FastInput::strip_cr(
+                &self.data[start..end],
+            )))
+        }
+    }
+
+    /// Reads the next line and returns it.
+    ///
+    /// Returns an empty string if there is no more data.
+    pub fn next_line(&self) -> &str {
+        self.try_next_line().unwrap_or("")
+    }
+
+    /// Reads the next line and returns an iterator over the parsed elements of the line.
+    ///
+    /// For more information, see [`FastInput::next_as_iter`].
+    pub fn next_as_iter<'a, T: FParse<'a>>(&'a self) -> impl Iterator<Item = T> + 'a {
+        self.next_split().map(|x| T::fparse(x))
+    }
+
+    /// Reads the next line and returns an iterator over the elements (no parsing).
+    ///
+    /// For more information, see [`FastInput::next_split`].
+    pub fn next_split(&self) -> impl Iterator<Item = &str> + '_ {
+        self.next_line().trim().split(' ')
+    }
+
+    /// Reads the next whitespace separated token and parses it, or returns
+    /// `None` if there are no more tokens.
+    ///
+    /// For more information, see [`FastInput::next_token`].
+    pub fn try_next_token<'a, T: FParse<'a>>(&'a self) -> Option<T> {
+        let (pos, end) = self.claim(|pos| {
+            let rest = &self.data[pos..];
+            let start = pos + rest.iter().position(|b| !b.is_ascii_whitespace())?;
+            let end = self.data[start..]
+                .iter()
+                .position(u8::is_ascii_whitespace)
+                .map_or(self.data.len(), |i| start + i);
+            Some((end, end))
+        })?;
+        let token = unsafe { from_utf8_unchecked(&self.data[pos..end]) };
+        Some(T::fparse(
+            token.trim_start_matches(|c: char| c.is_ascii_whitespace()),
+        ))
+    }
+
+    /// Checks if there is more data available in the buffer.
+    ///
+    /// Note that another thread may consume the remaining data between
+    /// this check and a following read, prefer [`try_next_line`](SyncFastInput::try_next_line).
+    pub fn has_next_line(&self) -> bool {
+        self.pos.load(Ordering::Acquire) != self.data.len()
+    }
+
+    /// Returns an iterator over lines, claiming each line as it is yielded.
+    pub fn lines(&self) -> impl Iterator<Item = &str> + '_ {
+        std::iter::from_fn(move || self.try_next_line())
+    }
+
+    /// Atomically advances the position. `region` maps the current position to
+    /// the end of the claimed region and the new position.
+    fn claim<F>(&self, region: F) -> Option<(usize, usize)>
+    where
+        F: Fn(usize) -> Option<(usize, usize)>,
+    {
+        let mut pos = self.pos.load(Ordering::Acquire);
+        loop {
+            let (end, next) = region(pos)?;
+            match self
+                .pos
+                .compare_exchange_weak(pos, next, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(_) => return Some((pos, end)),
+                Err(current) => pos = current,
+            }
+        }
+    }
+}
+
+impl Default for SyncFastInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
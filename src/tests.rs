@@ -1,6 +1,8 @@
 use super::*;
+use std::panic::AssertUnwindSafe;
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_empty() {
     let data = "".as_bytes();
     let input = FastInput::with_reader(data);
@@ -86,3 +88,1719 @@ fn read_all_lines() {
         assert_eq!(act, &truth);
     }
 }
+
+#[test]
+fn read_crlf_lines() {
+    let input = FastInput::with_reader("a b\r\nc d\r\n".as_bytes());
+    let first: Vec<_> = input.next_split().collect();
+    assert_eq!(["a", "b"], first[..]);
+    let (c, d): (Str, Str) = input.next();
+    assert_eq!(("c", "d"), (*c, *d));
+    assert!(!input.has_next_line());
+}
+
+#[test]
+fn read_crlf_as_iter() {
+    let input = FastInput::with_reader("1 2\r\n3\r\n".as_bytes());
+    let read: Vec<i32> = input.next_as_iter().collect();
+    assert_eq!([1, 2], read[..]);
+    assert_eq!(3, input.next_parsed());
+}
+
+#[test]
+fn try_next_line_at_eof() {
+    let input = FastInput::with_reader("a\nb\n".as_bytes());
+    assert_eq!(Some("a"), input.try_next_line());
+    assert_eq!(Some("b"), input.try_next_line());
+    assert_eq!(None, input.try_next_line());
+    assert_eq!(None, input.try_next_line());
+    assert!(!input.has_next_line());
+}
+
+#[test]
+fn try_read_tuple() {
+    let input = FastInput::with_reader("1 2\n1 x\n1\n1 2 3 4 5".as_bytes());
+    let ok: Result<(u8, u8), _> = input.try_next();
+    assert_eq!(Ok((1, 2)), ok);
+    let invalid: Result<(u8, u8), _> = input.try_next();
+    assert_eq!(
+        Err(ParseError::InvalidToken {
+            token: "x".to_owned(),
+            expected: "u8",
+            position: 6
+        }),
+        invalid
+    );
+    let missing: Result<(u8, u8), _> = input.try_next();
+    assert_eq!(Err(ParseError::MissingToken { position: 9 }), missing);
+    let quintuple: Result<(u8, u8, u8, u8, u8), _> = input.try_next();
+    assert_eq!(Ok((1, 2, 3, 4, 5)), quintuple);
+}
+
+#[test]
+fn try_read_empty_token() {
+    let input = FastInput::with_reader("1  2".as_bytes());
+    let read: Result<(i32, i32, i32), _> = input.try_next();
+    assert_eq!(Err(ParseError::MissingToken { position: 2 }), read);
+}
+
+#[test]
+fn read_with_delimiter() {
+    let input = FastInput::with_reader("1,2\n3,4,5\n6,x".as_bytes()).with_delimiter(',');
+    assert_eq!((1, 2), input.next());
+    let read: Vec<i32> = input.next_as_iter().collect();
+    assert_eq!([3, 4, 5], read[..]);
+    let invalid: Result<(i32, i32), _> = input.try_next();
+    assert!(matches!(invalid, Err(ParseError::InvalidToken { position: 12, .. })));
+}
+
+#[test]
+fn read_split_by() {
+    let input = FastInput::with_reader("a,,b\nc d".as_bytes());
+    let read: Vec<_> = input.next_split_by(',').collect();
+    assert_eq!(["a", "", "b"], read[..]);
+    let read: Vec<_> = input.next_split().collect();
+    assert_eq!(["c", "d"], read[..]);
+}
+
+#[test]
+fn read_tokens_across_lines() {
+    let input = FastInput::with_reader(" 1\t2\r\n\n3 four\n  ".as_bytes());
+    assert_eq!(1, input.next_token());
+    assert_eq!(Some(2), input.try_next_token());
+    assert!(input.has_next_token());
+    assert_eq!(3u8, input.next_token());
+    let word: Str = input.next_token();
+    assert_eq!("four", *word);
+    assert!(!input.has_next_token());
+    assert_eq!(None, input.try_next_token::<i32>());
+}
+
+#[test]
+fn read_token_then_line() {
+    let input = FastInput::with_reader("1 rest of line\nnext".as_bytes());
+    assert_eq!(1, input.next_token());
+    assert_eq!(" rest of line", input.next_line());
+    assert_eq!("next", input.next_line());
+}
+
+#[test]
+fn read_array() {
+    let input = FastInput::with_reader("1 2 3 4\n5 6\n7 8 9".as_bytes());
+    let first: [u32; 3] = input.next_array();
+    assert_eq!([1, 2, 3], first);
+    assert_eq!(None, input.try_next_array::<u32, 3>());
+    assert_eq!(Some([7, 8, 9]), input.try_next_array());
+    assert_eq!(None, input.try_next_array::<u32, 1>());
+}
+
+#[test]
+fn read_vec_across_lines() {
+    let input = FastInput::with_reader("1 2\n3\n\n4 5 6".as_bytes());
+    assert_eq!(vec![1, 2, 3, 4], input.next_vec::<i32>(4));
+    assert_eq!(Vec::<i32>::new(), input.next_vec(0));
+    assert_eq!(" 5 6", input.next_line());
+}
+
+#[test]
+#[should_panic(expected = "expected 3 tokens but input ended after 2")]
+fn read_vec_too_short() {
+    let input = FastInput::with_reader("1 2\n".as_bytes());
+    input.next_vec::<i32>(3);
+}
+
+#[test]
+fn reset_and_seek() {
+    let input = FastInput::with_reader("abc def\nghi".as_bytes());
+    assert_eq!("abc def", input.next_line());
+    input.reset();
+    assert_eq!("abc def", input.next_line());
+    input.seek(4);
+    assert_eq!("def", input.next_line());
+    input.seek(11);
+    assert!(!input.has_next_line());
+}
+
+#[test]
+#[should_panic]
+fn seek_out_of_bounds() {
+    let input = FastInput::with_reader("abc".as_bytes());
+    input.seek(4);
+}
+
+#[test]
+#[should_panic(expected = "seek position 2 is not a character boundary")]
+fn seek_inside_char() {
+    let input = FastInput::from_str("a\u{e9}");
+    input.seek(2);
+}
+
+#[test]
+fn read_remaining() {
+    let input = FastInput::with_reader("a\nb\n".as_bytes());
+    assert_eq!("a\nb\n", input.remaining());
+    input.next_line();
+    input.next_line();
+    assert_eq!("", input.remaining());
+}
+
+#[test]
+fn peek_line_does_not_consume() {
+    let input = FastInput::with_reader("first\r\nsecond".as_bytes());
+    assert_eq!(Some("first"), input.peek_line());
+    assert_eq!(Some("first"), input.peek_line());
+    assert_eq!("first", input.next_line());
+    assert_eq!(Some("second"), input.peek_line());
+    assert_eq!("second", input.next_line());
+    assert_eq!(None, input.peek_line());
+}
+
+#[test]
+fn memchr_finds_first_match() {
+    for len in 0..40 {
+        for at in 0..len {
+            let mut data = vec![b'a'; len];
+            data[at] = b'\n';
+            if at + 1 < len {
+                data[len - 1] = b'\n';
+            }
+            assert_eq!(Some(at), memchr(b'\n', &data));
+        }
+        assert_eq!(None, memchr(b'\n', &vec![0x8a; len]));
+    }
+}
+
+#[test]
+fn read_long_lines() {
+    let lines: Vec<String> = (0..50).map(|i| "x".repeat(i)).collect();
+    let src = lines.join("\n");
+    let input = FastInput::with_reader(src.as_bytes());
+    let read: Vec<_> = input.lines().collect();
+    assert_eq!(lines, read);
+}
+
+/// Reader returning one chunk per call to `read`.
+struct Chunked(std::collections::VecDeque<Vec<u8>>);
+
+impl Read for Chunked {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.0.pop_front() {
+            Some(chunk) => {
+                buf[..chunk.len()].copy_from_slice(&chunk);
+                Ok(chunk.len())
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+fn streaming<'a>(chunks: impl IntoIterator<Item = &'a str>) -> FastInput {
+    let chunks = chunks.into_iter().map(|c| c.as_bytes().to_vec()).collect();
+    FastInput::from_source(Box::new(BufReader::new(Chunked(chunks))))
+}
+
+#[test]
+fn streaming_reads_lazily() {
+    let input = streaming(vec!["1 2\n", "3 ", "4\n", "5"]);
+    assert_eq!((1, 2), input.next());
+    assert_eq!(4, input.data().len());
+    assert_eq!((3, 4), input.next());
+    assert_eq!(8, input.data().len());
+    assert_eq!(5, input.next_token());
+    assert!(!input.has_next_line());
+    assert!(!input.has_next_token());
+}
+
+#[test]
+fn streaming_keeps_lines_valid() {
+    let line = "x".repeat(BUFFER_SIZE / 3);
+    let src = format!("{}\n", line);
+    let input = streaming(vec![src.as_str(); 20]);
+    let first = input.next_line();
+    let rest: Vec<_> = input.lines().collect();
+    assert_eq!(line, first);
+    assert_eq!(19, rest.len());
+    assert!(rest.iter().all(|l| *l == line));
+}
+
+#[test]
+fn streaming_remaining_and_seek() {
+    let input = streaming(vec!["a\n", "b\n", "c"]);
+    input.seek(2);
+    assert_eq!("b", input.next_line());
+    assert_eq!("c", input.remaining());
+    assert_eq!("c", input.next_line());
+    assert_eq!(None, input.try_next_line());
+}
+
+#[test]
+fn read_owned_lines() {
+    let input = FastInput::with_reader("a\nb\nc".as_bytes());
+    let first: String = input.next_line_owned();
+    let rest: Vec<String> = input.owned_lines().collect();
+    assert_eq!("a", first);
+    assert_eq!(["b", "c"], rest[..]);
+}
+
+#[test]
+fn count_lines() {
+    for (src, lines) in [("", 0), ("a", 1), ("a\n", 1), ("a\nb", 2), ("\n\n", 2)] {
+        let input = FastInput::with_reader(src.as_bytes());
+        assert_eq!(lines, input.total_lines());
+        assert_eq!(lines, input.remaining_lines());
+        assert_eq!(lines, input.lines().count());
+        assert_eq!(0, input.remaining_lines());
+        assert_eq!(lines, input.total_lines());
+    }
+}
+
+type Twelve = (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8);
+
+#[test]
+fn read_twelve_tuple() {
+    let data = "1 2 3 4 5 6 7 8 9 10 11 12\n1 2 3 4 5 6 7 8 9 10 11 x";
+    let input = FastInput::with_reader(data.as_bytes());
+    let read: Twelve = input.next();
+    assert_eq!((1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12), read);
+    let read: Result<Twelve, _> = input.try_next();
+    assert!(matches!(read, Err(ParseError::InvalidToken { position: 51, .. })));
+}
+
+#[test]
+#[should_panic(expected = "expected 3 elements on line")]
+fn read_tuple_too_few_elements() {
+    let input = FastInput::with_reader("1 2".as_bytes());
+    let _: (i32, i32, i32) = input.next();
+}
+
+#[test]
+fn read_bool() {
+    let input = FastInput::with_reader("1 0 true FALSE Yes no\n1 maybe".as_bytes());
+    let read: Vec<bool> = input.next_as_iter::<Bool>().map(|b| *b).collect();
+    assert_eq!([true, false, true, false, true, false], read[..]);
+    let read: Result<(Bool, Bool), _> = input.try_next();
+    assert!(matches!(read, Err(ParseError::InvalidToken { expected: "fast_input::Bool", .. })));
+}
+
+#[test]
+#[should_panic(expected = "invalid boolean \"maybe\"")]
+fn read_invalid_bool() {
+    let input = FastInput::with_reader("maybe".as_bytes());
+    let _: Bool = input.next_parsed();
+}
+
+#[test]
+fn read_char() {
+    let input = FastInput::with_reader("a bc ö\n".as_bytes());
+    let (a, b, o): (Char, Char, Char) = input.next();
+    assert_eq!(('a', 'b', 'ö'), (*a, *b, *o));
+}
+
+#[test]
+#[should_panic(expected = "empty token")]
+fn read_empty_char() {
+    let input = FastInput::with_reader("a  b".as_bytes());
+    let _: Vec<Char> = input.next_as_iter().collect();
+}
+
+#[test]
+fn read_grids() {
+    let input = FastInput::with_reader("2 3\n1 2 3\n4 5 6\n#.\r\n.#\n".as_bytes());
+    let (rows, _cols): (usize, usize) = input.next();
+    let grid: Vec<Vec<i32>> = input.next_grid(rows);
+    assert_eq!(vec![vec![1, 2, 3], vec![4, 5, 6]], grid);
+    let chars = input.next_char_grid(2);
+    assert_eq!(vec![vec!['#', '.'], vec!['.', '#']], chars);
+}
+
+#[test]
+#[should_panic(expected = "expected 2 rows")]
+fn read_char_grid_too_few_rows() {
+    let input = FastInput::with_reader("#.".as_bytes());
+    input.next_char_grid(2);
+}
+
+#[test]
+fn sync_input_claims_each_line_once() {
+    let src: Vec<String> = (1..=1000).map(|i| i.to_string()).collect();
+    let input = SyncFastInput::with_reader(src.join("\r\n").as_bytes());
+    let mut read: Vec<u32> = std::thread::scope(|s| {
+        let workers: Vec<_> = (0..4)
+            .map(|_| s.spawn(|| input.lines().map(|l| l.parse::<u32>().unwrap()).collect::<Vec<_>>()))
+            .collect();
+        workers.into_iter().flat_map(|w| w.join().unwrap()).collect()
+    });
+    read.sort_unstable();
+    assert_eq!((1..=1000).collect::<Vec<_>>(), read);
+    assert!(!input.has_next_line());
+}
+
+#[test]
+fn sync_input_skips_bom() {
+    let input = SyncFastInput::with_reader("\u{feff}42\n".as_bytes());
+    assert_eq!(Some("42"), input.try_next_line());
+    assert!(!input.has_next_line());
+}
+
+#[test]
+fn streaming_skips_split_bom() {
+    let chunks = vec![b"\xEF".to_vec(), b"\xBB\xBF42".to_vec()];
+    let input = FastInput::streaming_from(Chunked(chunks.into()));
+    assert_eq!(Some("42"), input.peek_token());
+    let chunks = vec![b"\xEF\xBB".to_vec(), b"\xBF".to_vec(), b"4".to_vec(), b"2".to_vec()];
+    let input = FastInput::streaming_from(Chunked(chunks.into()));
+    assert_eq!(42, input.next_token::<u32>());
+    let chunks = vec![b"\xEF".to_vec(), b"\xBB".to_vec(), b"!\n".to_vec()];
+    let input = FastInput::streaming_from(Chunked(chunks.into()));
+    assert_eq!(b"\xEF\xBB!", input.next_line_bytes());
+}
+
+#[test]
+fn skipped_bom_is_not_part_of_input() {
+    let input = FastInput::with_reader("\u{feff}a\nb".as_bytes());
+    assert_eq!(b"a\nb", input.as_bytes());
+    assert_eq!("a", input.next_line());
+    assert_eq!(2, input.position());
+    input.reset();
+    assert_eq!("a", input.next_line());
+    assert_eq!(b"a\nb", input.clone().as_bytes());
+}
+
+#[test]
+fn sync_input_tokens() {
+    let input = SyncFastInput::with_reader(" 1 2\n\n 3 ".as_bytes());
+    let mut read = Vec::new();
+    while let Some(token) = input.try_next_token::<i32>() {
+        read.push(token);
+    }
+    assert_eq!([1, 2, 3], read[..]);
+}
+
+#[test]
+fn fast_output() {
+    use std::io::Write;
+    let mut output = FastOutput::with_writer(Vec::new());
+    output.write_str("a");
+    output.writeln("b");
+    output.put(1);
+    output.putln(Str("c"));
+    writeln!(output, "{} {}", 2, 3).unwrap();
+    assert_eq!(2, output.write(b"d\n").unwrap());
+    assert_eq!("ab\n1c\n2 3\nd\n", String::from_utf8(output.into_inner()).unwrap());
+}
+
+#[test]
+fn read_int() {
+    let input = FastInput::with_reader("0 -0 +42\n-2147483648 2147483647\n\n-9223372036854775808 255".as_bytes());
+    assert_eq!(0u8, input.next_int());
+    assert_eq!(0u8, input.next_int());
+    assert_eq!(42usize, input.next_int());
+    assert_eq!(i32::MIN, input.next_int());
+    assert_eq!(i32::MAX, input.next_int());
+    assert_eq!(i64::MIN, input.next_int());
+    assert_eq!(255u8, input.next_int());
+    assert!(!input.has_next_token());
+}
+
+#[test]
+fn from_digits_rejects_invalid() {
+    assert_eq!(None, u8::from_digits(b"256", false));
+    assert_eq!(None, i8::from_digits(b"129", true));
+    assert_eq!(Some(-128), i8::from_digits(b"128", true));
+    assert_eq!(None, u32::from_digits(b"1", true));
+    assert_eq!(None, i32::from_digits(b"", false));
+    assert_eq!(None, i32::from_digits(b"12a", false));
+    assert_eq!(None, i32::from_digits(b"-1", false));
+}
+
+#[test]
+#[should_panic(expected = "invalid integer \"-\" for type i32")]
+fn read_invalid_int() {
+    let input = FastInput::with_reader("- 1".as_bytes());
+    input.next_int::<i32>();
+}
+
+#[test]
+fn read_tuples() {
+    let input = FastInput::with_reader("1 2\n3 4\n".as_bytes());
+    let read: Vec<(i32, i32)> = input.tuples().collect();
+    assert_eq!(vec![(1, 2), (3, 4)], read);
+    let input = FastInput::with_reader("a 1 x\nb 2 y".as_bytes());
+    let read: Vec<(Str, u8, Char)> = input.tuples().collect();
+    assert_eq!("b", *read[1].0);
+    assert_eq!(2, read[1].1);
+}
+
+#[test]
+fn read_tuples_trailing_blank_lines() {
+    for data in &["1 2\n\n", "1 2\n   \n"] {
+        let input = FastInput::from_str(data);
+        let read: Vec<(u32, u32)> = input.tuples().collect();
+        assert_eq!(vec![(1, 2)], read);
+    }
+}
+
+#[test]
+fn skip_lines() {
+    let input = FastInput::with_reader("a\nb\nc\nd\ne".as_bytes());
+    input.skip_line();
+    input.skip_lines(2);
+    assert_eq!("d", input.next_line());
+    assert!(!input.try_skip_lines(2));
+    assert!(!input.has_next_line());
+    assert!(input.try_skip_lines(0));
+}
+
+#[test]
+#[should_panic(expected = "fewer than 3 lines to skip")]
+fn skip_too_many_lines() {
+    let input = FastInput::with_reader("a\nb\n".as_bytes());
+    input.skip_lines(3);
+}
+
+#[test]
+fn read_without_trim() {
+    let input = FastInput::with_reader(" 1 2 \n 3\n".as_bytes()).with_trim(false);
+    let read: Vec<_> = input.next_split().collect();
+    assert_eq!(["", "1", "2", ""], read[..]);
+    let read: Result<(i32, i32), _> = input.try_next();
+    assert_eq!(Err(ParseError::MissingToken { position: 6 }), read);
+}
+
+#[test]
+fn read_whitespace_split() {
+    let input = FastInput::with_reader("1    2\t\t3 \r\n\n4".as_bytes());
+    let read: Vec<i32> = input.next_whitespace_split().map(|t| t.parse().unwrap()).collect();
+    assert_eq!([1, 2, 3], read[..]);
+    assert_eq!(0, input.next_whitespace_split().count());
+    assert_eq!(1, input.next_whitespace_split().count());
+}
+
+#[test]
+fn from_bytes_and_str() {
+    let input = FastInput::from_bytes(vec![b'1', b' ', b'2']);
+    assert_eq!((1, 2), input.next());
+    let input = FastInput::from_str("a\nb");
+    assert_eq!(vec!["a", "b"], input.lines().collect::<Vec<_>>());
+}
+
+#[test]
+fn read_line_bytes() {
+    let input = FastInput::with_reader(&b"\x00\x01\r\n\xfe"[..]);
+    assert_eq!(b"\x00\x01", input.next_line_bytes());
+    assert_eq!(b"\xfe", input.next_line_bytes());
+    assert!(!input.has_next_line());
+}
+
+#[test]
+fn read_radix() {
+    let input = FastInput::with_reader("ff 0Xff -101\n0b11 0o17 z".as_bytes());
+    assert_eq!(255u8, input.next_radix(16));
+    assert_eq!(255u32, input.next_radix(16));
+    assert_eq!(-5i8, input.next_radix(2));
+    assert_eq!(3u8, input.next_radix(2));
+    assert_eq!(15u8, input.next_radix(8));
+    assert_eq!(35u8, input.next_radix(36));
+}
+
+#[test]
+fn read_signed_prefixed_radix() {
+    let input = FastInput::with_reader("-0x10 +0b11 -0O17 0x-1 -ff".as_bytes());
+    assert_eq!(-16i32, input.next_radix(16));
+    assert_eq!(3i8, input.next_radix(2));
+    assert_eq!(-15i64, input.next_radix(8));
+    assert_eq!(None, i32::try_fparse_radix(input.next_word(), 16));
+    assert_eq!(-255i32, input.next_radix(16));
+}
+
+#[test]
+#[should_panic(expected = "invalid base 2 integer \"12\"")]
+fn read_invalid_radix() {
+    let input = FastInput::with_reader("12".as_bytes());
+    input.next_radix::<u8>(2);
+}
+
+#[test]
+fn lines_iterator() {
+    let input = FastInput::with_reader("a\nb\nc\n".as_bytes());
+    let mut lines = input.lines();
+    assert_eq!((1, Some(6)), lines.size_hint());
+    assert_eq!(Some("a"), lines.next());
+    assert_eq!((1, Some(4)), lines.size_hint());
+    let rest: Vec<_> = (&input).into_iter().collect();
+    assert_eq!(["b", "c"], rest[..]);
+    assert_eq!(None, lines.next());
+    assert_eq!((0, Some(0)), lines.size_hint());
+}
+
+#[test]
+fn streaming_lines_size_hint() {
+    let input = streaming(vec!["a\nb", "\nc"]);
+    assert!(input.has_next_line());
+    assert_eq!((1, None), input.lines().size_hint());
+    let mut count = 0;
+    for _ in &input {
+        count += 1;
+    }
+    assert_eq!(3, count);
+}
+
+#[test]
+fn lines_trailing_and_blank() {
+    let cases: [(&str, &[&str]); 7] = [
+        ("a\nb\n", &["a", "b"]),
+        ("a\nb", &["a", "b"]),
+        ("a\n\nb", &["a", "", "b"]),
+        ("a\n\n", &["a", ""]),
+        ("\n", &[""]),
+        ("\n\n", &["", ""]),
+        ("a\r\n\r\nb\r\n", &["a", "", "b"]),
+    ];
+    for (src, expected) in cases.iter() {
+        let input = FastInput::with_reader(src.as_bytes());
+        let read: Vec<_> = input.lines().collect();
+        assert_eq!(expected, &&read[..], "input {:?}", src);
+    }
+}
+
+#[test]
+fn peek_token_does_not_consume() {
+    let input = FastInput::with_reader("  a\n b".as_bytes());
+    assert_eq!(Some("a"), input.peek_token());
+    assert_eq!(Some("a"), input.peek_token());
+    let a: Str = input.next_token();
+    assert_eq!("a", *a);
+    assert_eq!(Some("b"), input.peek_token());
+    input.next_token::<Str>();
+    assert_eq!(None, input.peek_token());
+}
+
+#[test]
+fn read_set_and_map() {
+    let input = FastInput::with_reader("1 2 2 3\na 1 b 2 a 3".as_bytes());
+    let set: HashSet<u8> = input.next_set();
+    assert_eq!([1, 2, 3].iter().copied().collect::<HashSet<_>>(), set);
+    let map: HashMap<char, i32> = input.next_map();
+    assert_eq!(2, map.len());
+    assert_eq!(3, map[&'a']);
+}
+
+#[test]
+#[should_panic(expected = "odd number of elements on line, key \"b\" has no value")]
+fn read_map_odd_elements() {
+    let input = FastInput::with_reader("a 1 b".as_bytes());
+    input.next_map::<char, i32>();
+}
+
+#[test]
+fn read_line_as() {
+    let input = FastInput::with_reader("  12  \n1.5e3\n127.0.0.1".as_bytes());
+    assert_eq!(12, input.next_line_as::<u32>());
+    assert_eq!(1500.0, input.next_line_as::<f64>());
+    let ip: std::net::Ipv4Addr = input.next_line_as();
+    assert_eq!(std::net::Ipv4Addr::new(127, 0, 0, 1), ip);
+}
+
+#[test]
+fn position_and_len() {
+    let input = FastInput::with_reader("12 34\n56".as_bytes());
+    assert_eq!(0, input.position());
+    assert_eq!(8, input.len());
+    assert!(!input.is_empty());
+    input.next_token::<u32>();
+    let saved = input.position();
+    assert_eq!(2, saved);
+    input.next_line();
+    input.seek(saved);
+    assert_eq!(34, input.next_token());
+    assert!(FastInput::from_str("").is_empty());
+    assert!(!streaming(vec!["a"]).is_empty());
+    assert!(streaming(vec![]).is_empty());
+}
+
+#[cfg(all(feature = "mmap", unix, target_pointer_width = "64"))]
+#[test]
+fn read_mmap() {
+    let path = std::env::temp_dir().join(format!("fast_input_mmap_{}", std::process::id()));
+    std::fs::write(&path, "1 2\nabc\n").unwrap();
+    let input = unsafe { FastInput::from_file_mmap(&path).unwrap() };
+    assert_eq!((1, 2), input.next());
+    assert_eq!("abc", input.next_line());
+    assert!(!input.has_next_line());
+    assert_eq!(8, input.len());
+    std::fs::write(&path, "").unwrap();
+    let empty = unsafe { FastInput::from_file_mmap(&path).unwrap() };
+    assert!(empty.is_empty());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn read_tuple_by() {
+    let input = FastInput::with_reader("12:34\nkey=a=b\n1 2".as_bytes());
+    assert_eq!((12, 34), input.next_tuple_by(':'));
+    let (key, value): (Str, Str) = input.next_tuple_by('=');
+    assert_eq!(("key", "a=b"), (*key, *value));
+}
+
+#[test]
+#[should_panic(expected = "separator ':' not found in line \"1 2\"")]
+fn read_tuple_by_missing_separator() {
+    let input = FastInput::with_reader("1 2".as_bytes());
+    input.next_tuple_by::<i32, i32>(':');
+}
+
+#[test]
+fn checked_utf8() {
+    let input = FastInput::with_reader_checked("å\nä".as_bytes()).unwrap();
+    assert_eq!(vec!["å", "ä"], input.lines().collect::<Vec<_>>());
+    let err = FastInput::with_reader_checked(&b"ab\n\xc3"[..]).err().unwrap();
+    assert_eq!(3, err.valid_up_to());
+}
+
+#[test]
+fn line_scanner() {
+    let input = FastInput::with_reader("a 1  2\t3 \r\n4\n".as_bytes());
+    let mut scanner = input.line_scanner();
+    let a: Char = scanner.next();
+    assert_eq!('a', *a);
+    assert_eq!(1, scanner.next());
+    assert!(scanner.has_next());
+    assert_eq!("  2\t3 ", input.peek_line().unwrap());
+    assert_eq!(Some(2), scanner.try_next());
+    assert_eq!(Some(3), scanner.try_next());
+    assert!(!scanner.has_next());
+    assert_eq!(None, scanner.try_next::<i32>());
+    assert_eq!(" ", input.next_line());
+    assert_eq!(4, input.next_parsed());
+}
+
+#[test]
+fn debug_shows_cursor() {
+    let input = FastInput::with_reader("1 2\n\tå\x01".as_bytes());
+    input.next_line();
+    assert_eq!(
+        "FastInput { position: 4, len: 8, remaining: \"\\t\\xc3\\xa5\\x01\" }",
+        format!("{:?}", input)
+    );
+    assert_eq!(4, input.position());
+    let long = FastInput::from_str(&"x".repeat(100));
+    let debug = format!("{:?}", long);
+    assert!(debug.ends_with(&format!("\"{}\"... }}", "x".repeat(40))));
+}
+
+/// Reader failing after returning some data.
+struct Failing(bool);
+
+impl Read for Failing {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.0 {
+            return Err(std::io::Error::new(ErrorKind::BrokenPipe, "broken"));
+        }
+        self.0 = true;
+        buf[0] = b'a';
+        Ok(1)
+    }
+}
+
+#[test]
+fn try_with_reader_propagates_errors() {
+    let err = FastInput::try_with_reader(Failing(false)).err().unwrap();
+    assert_eq!(ErrorKind::BrokenPipe, err.kind());
+    let input = FastInput::try_with_reader("a".as_bytes()).unwrap();
+    assert_eq!("a", input.next_line());
+}
+
+#[test]
+fn read_float_matches_std() {
+    let tokens = [
+        "0", "-0", "1", "-1.5", "+2.25", "3.", ".5", "1e10", "1E-5", "123456.789e-3",
+        "0.1", "0.000001", "9007199254740993", "1234567890123456789", "12345678901234567890",
+        "1e22", "1e23", "1e-22", "1e-400", "1e400", "4.9e-324", "inf", "-infinity", "NaN",
+        "3.4028235e38", "0.30000000000000004",
+    ];
+    let src = tokens.join(" ");
+    let input = FastInput::with_reader(src.as_bytes());
+    for token in tokens.iter() {
+        let read: f64 = input.next_float();
+        let expected: f64 = token.parse().unwrap();
+        assert!(read.to_bits() == expected.to_bits() || read.is_nan() && expected.is_nan(), "{}", token);
+    }
+    input.reset();
+    for token in tokens.iter() {
+        let read: f32 = input.next_float();
+        let expected: f32 = token.parse().unwrap();
+        assert!(read.to_bits() == expected.to_bits() || read.is_nan() && expected.is_nan(), "{}", token);
+    }
+}
+
+#[test]
+fn parse_invalid_floats() {
+    for token in ["", "-", ".", "1.2.3", "1e", "1e+", "e5", "1x", "--1"].iter() {
+        assert_eq!(None, f64::from_bytes(token.as_bytes()), "{}", token);
+    }
+}
+
+#[test]
+fn read_n_tuples() {
+    let input = FastInput::with_reader("3\n1 2\n2 3\n3 1\n0\n".as_bytes());
+    let edges: Vec<(u32, u32)> = input.read_n().collect();
+    assert_eq!(vec![(1, 2), (2, 3), (3, 1)], edges);
+    assert_eq!(0, input.read_n::<(u8, u8)>().count());
+    assert!(!input.has_next_line());
+}
+
+#[test]
+#[should_panic(expected = "failed to parse line")]
+fn read_n_invalid_count() {
+    let input = FastInput::with_reader("1 2\n".as_bytes());
+    input.read_n::<(u8, u8)>().count();
+}
+
+#[test]
+fn reader_with_capacity() {
+    let input = FastInput::with_reader_capacity("1 2\n3 4\n".as_bytes(), 0);
+    let pairs: Vec<(u8, u8)> = input.tuples().collect();
+    assert_eq!(vec![(1, 2), (3, 4)], pairs);
+}
+
+#[test]
+fn compare_str() {
+    let input = FastInput::with_reader("YES a b a".as_bytes());
+    let (answer, x, y, z): (Str, Str, Str, Str) = input.next();
+    assert!(answer == "YES");
+    assert!(answer != "NO");
+    assert!(answer == *"YES");
+    assert!(x == z && x != y);
+
+    let mut counts = HashMap::new();
+    for token in [x, y, z] {
+        *counts.entry(token).or_insert(0) += 1;
+    }
+    assert_eq!(2, counts[&Str("a")]);
+}
+
+#[test]
+fn str_as_ref_and_borrow() {
+    fn length(s: impl AsRef<str>) -> usize {
+        s.as_ref().len()
+    }
+    let input = FastInput::with_reader("apple pear apple".as_bytes());
+    let mut counts: HashMap<Str, u32> = HashMap::new();
+    for _ in 0..3 {
+        let fruit: Str = input.next_token();
+        *counts.entry(fruit).or_insert(0) += 1;
+    }
+    assert_eq!(Some(&2), counts.get("apple"));
+    assert_eq!(Some(&1), counts.get("pear"));
+    assert_eq!(None, counts.get("plum"));
+    let word = Str("word");
+    assert_eq!(4, length(word));
+}
+
+#[test]
+fn take_until_sentinel() {
+    let input = FastInput::with_reader("1 2\n0 0\n3 4\n-1 -1\n5 6\n".as_bytes());
+    let first: Vec<(i32, i32)> = input.take_until((0, 0)).collect();
+    let second: Vec<(i32, i32)> = input.take_until((-1, -1)).collect();
+    let rest: Vec<(i32, i32)> = input.take_until((0, 0)).collect();
+    assert_eq!(vec![(1, 2)], first);
+    assert_eq!(vec![(3, 4)], second);
+    assert_eq!(vec![(5, 6)], rest);
+    assert!(!input.has_next_line());
+}
+
+#[test]
+fn read_words() {
+    let input = FastInput::with_reader("a bc\r\nnext line\nx  \n\ny".as_bytes());
+    assert_eq!("a", input.next_word());
+    assert_eq!("bc", input.next_word());
+    assert_eq!("next line", input.next_line());
+    assert_eq!("x", input.next_word());
+    assert_eq!(" ", input.next_line());
+    assert_eq!("y", input.next_word());
+    assert!(!input.has_next_token());
+}
+
+#[test]
+fn read_words_streaming() {
+    let input = streaming(vec!["ab\r", "\ncd", " e"]);
+    assert_eq!("ab", input.next_word());
+    assert_eq!("cd", input.next_word());
+    assert_eq!("e", input.next_word());
+    assert!(!input.has_next_line());
+}
+
+#[test]
+fn read_optional_values() {
+    let input = FastInput::with_reader("1  3\nnull 2 null\n".as_bytes());
+    let first: Vec<Option<i32>> = input.next_as_iter_opt().collect();
+    assert_eq!(vec![Some(1), None, Some(3)], first);
+
+    let input = FastInput::with_reader("null 2 null\n".as_bytes()).with_null_token("null");
+    let second: Vec<Option<i32>> = input.next_as_iter_opt().collect();
+    assert_eq!(vec![None, Some(2), None], second);
+}
+
+#[test]
+fn read_splitn() {
+    let input = FastInput::with_reader(" set key a b c \nx\n".as_bytes());
+    let parts: Vec<_> = input.next_splitn(3).collect();
+    assert_eq!(vec!["set", "key", "a b c"], parts);
+    let parts: Vec<_> = input.next_splitn(3).collect();
+    assert_eq!(vec!["x"], parts);
+    assert!(!input.has_next_line());
+}
+
+#[test]
+fn checkpoint_and_restore() {
+    let input = FastInput::with_reader("1 2\n3 x\n".as_bytes());
+    let start = input.checkpoint();
+    let _: (u8, u8) = input.next();
+    let second = input.checkpoint();
+    let failed: Result<(u8, u8), _> = input.try_next();
+    assert!(failed.is_err());
+    input.restore(second);
+    assert_eq!("3 x", input.next_line());
+    input.restore(start);
+    assert_eq!("1 2", input.next_line());
+    assert_ne!(start, second);
+}
+
+#[test]
+fn count_tokens_without_consuming() {
+    let input = FastInput::with_reader(" 1 2 3 \n\n".as_bytes());
+    assert_eq!(3, input.peek_token_count());
+    assert_eq!(3, input.peek_token_count());
+    assert_eq!(" 1 2 3 ", input.next_line());
+    assert_eq!(1, input.peek_token_count());
+    input.next_line();
+    let input = FastInput::with_reader("a,b\n".as_bytes()).with_delimiter(',');
+    assert_eq!(2, input.peek_token_count());
+    input.next_line();
+    assert_eq!(0, input.peek_token_count());
+}
+
+#[test]
+fn read_owned_split() {
+    let input = FastInput::with_reader("a  b\nc\n".as_bytes());
+    let first = input.next_split_owned();
+    let second = input.next_split_owned();
+    drop(input);
+    assert_eq!(vec!["a", "", "b"], first);
+    assert_eq!(vec!["c"], second);
+}
+
+#[test]
+fn skip_byte_order_mark() {
+    let input = FastInput::with_reader("\u{FEFF}42\n\u{FEFF}".as_bytes());
+    assert_eq!(42, input.next_int::<i32>());
+    input.next_line();
+    assert_eq!("\u{FEFF}", input.next_line());
+
+    let input = FastInput::from_str("\u{FEFF}1 2");
+    let pair: (u8, u8) = input.next();
+    assert_eq!((1, 2), pair);
+
+    let input = streaming(vec!["\u{FEFF}7", " 8"]);
+    let pair: (u8, u8) = input.next();
+    assert_eq!((7, 8), pair);
+}
+
+#[test]
+fn buffered_reader() {
+    let input = FastInput::with_reader_buffered(Chunked(
+        vec![b"1 2\n3".to_vec(), b" 4\n".to_vec()].into(),
+    ));
+    let pairs: Vec<(u8, u8)> = input.tuples().collect();
+    assert_eq!(vec![(1, 2), (3, 4)], pairs);
+}
+
+#[test]
+fn split_by_any() {
+    let input = FastInput::with_reader(" 1, 2,,3 \n;\n".as_bytes());
+    let values: Vec<u32> = input.next_split_by_any(&[',', ' ']).map(|v| v.parse().unwrap()).collect();
+    assert_eq!(vec![1, 2, 3], values);
+    assert_eq!(0, input.next_split_by_any(&[';']).count());
+}
+
+#[test]
+fn line_numbers() {
+    let input = FastInput::with_reader("a\r\nb c\n\nd".as_bytes());
+    assert_eq!(1, input.current_line_number());
+    input.next_line();
+    assert_eq!(2, input.current_line_number());
+    input.next_token::<Str>();
+    assert_eq!(2, input.current_line_number());
+    input.next_line();
+    let lines: Vec<_> = input.enumerated_lines().collect();
+    assert_eq!(vec![(3, ""), (4, "d")], lines);
+    assert_eq!(4, input.current_line_number());
+}
+
+#[test]
+fn read_macro() {
+    let input = FastInput::with_reader("1 -2\nword x\n".as_bytes());
+    read!(input, a: u8, mut b: i64);
+    read!(input, w: Str, c: char,);
+    b -= 1;
+    assert_eq!((1, -3), (a, b));
+    assert!(w == "word");
+    assert_eq!('x', c);
+    read!(input);
+}
+
+#[test]
+fn read_paragraphs() {
+    let input = FastInput::with_reader("a\r\nb c\n \r\n\nd\ne".as_bytes());
+    assert_eq!(vec!["a", "b c"], input.next_paragraph());
+    assert!(input.next_paragraph().is_empty());
+    assert_eq!(vec!["d", "e"], input.next_paragraph());
+    assert!(input.next_paragraph().is_empty());
+}
+
+#[test]
+fn read_int_any() {
+    let input = FastInput::with_reader("a - -b -12,3\n--4x5".as_bytes());
+    assert_eq!(-12, input.next_int_any::<i64>());
+    assert_eq!(3, input.next_int_any::<u8>());
+    assert_eq!(-4, input.next_int_any::<i8>());
+    assert_eq!(5, input.next_int_any::<u32>());
+    assert!(!input.has_next_token());
+}
+
+#[test]
+fn read_int_any_streaming() {
+    let input = streaming(vec!["x -", "12 ", "3", "4,"]);
+    assert_eq!(-12, input.next_int_any::<i32>());
+    assert_eq!(34, input.next_int_any::<i32>());
+}
+
+#[test]
+#[should_panic(expected = "no more integers in input")]
+fn read_int_any_missing() {
+    let input = FastInput::with_reader("a, b, -".as_bytes());
+    input.next_int_any::<i32>();
+}
+
+#[test]
+fn clone_input() {
+    let input = FastInput::with_reader("1,2\n3,4\n".as_bytes()).with_delimiter(',');
+    input.next_line();
+    let copy = input.clone();
+    let first: (u8, u8) = input.next();
+    let second: (u8, u8) = copy.next();
+    assert_eq!((3, 4), first);
+    assert_eq!(first, second);
+    copy.reset();
+    assert_eq!("1,2", copy.next_line());
+    assert!(!input.has_next_line());
+
+    let input = streaming(vec!["a\n", "b\n"]);
+    let copy = input.clone();
+    assert_eq!(vec!["a", "b"], copy.lines().collect::<Vec<_>>());
+    assert_eq!(vec!["a", "b"], input.lines().collect::<Vec<_>>());
+}
+
+#[test]
+fn fork_input() {
+    let input = FastInput::with_reader("skip\n1 2\r\n3".as_bytes());
+    input.next_line();
+    let view = input.fork();
+    assert_eq!(1, view.next_token::<u8>());
+    assert_eq!(" 2", view.next_line());
+    let (a, b): (u8, u8) = input.next();
+    assert_eq!((1, 2), (a, b));
+    assert_eq!(Some("3"), view.try_next_line());
+    assert!(!view.has_next_line() && !view.has_next_token());
+    assert_eq!(None, view.try_next_token::<u8>());
+    view.reset();
+    assert_eq!(vec!["skip", "1 2", "3"], view.lines().collect::<Vec<_>>());
+    assert_eq!("3", input.next_line());
+}
+
+#[test]
+fn read_fixed_bytes() {
+    let input = streaming(vec!["ab", "c\nd", "e"]);
+    assert_eq!(b"abc\n", input.next_bytes(4));
+    assert_eq!(None, input.try_next_bytes(3));
+    assert_eq!(Some(&b""[..]), input.try_next_bytes(0));
+    assert_eq!(b"de", input.next_bytes(2));
+}
+
+#[test]
+#[should_panic(expected = "expected 3 more bytes")]
+fn read_fixed_bytes_past_end() {
+    let input = FastInput::with_reader("ab".as_bytes());
+    input.next_bytes(3);
+}
+
+#[test]
+fn read_fixed_bytes_at_char_boundaries() {
+    let chunks = vec![b"a\xc3".to_vec(), b"\xa9\n".to_vec()];
+    let input = FastInput::from_source(Box::new(BufReader::new(Chunked(chunks.into()))));
+    assert_eq!(None, input.try_next_bytes(2));
+    assert_eq!(None, input.try_next_bytes(usize::MAX));
+    assert_eq!(b"a", input.next_bytes(1));
+    assert_eq!("\u{e9}", input.next_line());
+}
+
+#[test]
+#[should_panic(expected = "expected 1 more bytes ending at a character boundary")]
+fn read_fixed_bytes_inside_char() {
+    let input = FastInput::from_str("\u{e9}\n");
+    input.next_bytes(1);
+}
+
+#[test]
+fn finish_partial_line() {
+    let input = FastInput::with_reader("1 2\r\n3 4\n5".as_bytes());
+    input.finish_line();
+    assert_eq!(1, input.line_scanner().next::<u8>());
+    input.finish_line();
+    input.finish_line();
+    assert_eq!("3 4", input.next_line());
+    input.finish_line();
+    assert_eq!(5, input.next_token::<u8>());
+    input.finish_line();
+    assert!(!input.has_next_line());
+}
+
+#[test]
+fn read_groups() {
+    let input = FastInput::with_reader("1,2 3,4\n5:6:7 a,1,true,x\n".as_bytes());
+    let pairs: Vec<[u8; 2]> = input.next_as_iter::<Group<[u8; 2]>>().map(|g| *g).collect();
+    assert_eq!(vec![[1, 2], [3, 4]], pairs);
+    let mut scanner = input.line_scanner();
+    let a: Group<(u8, u8, u8), ':'> = scanner.next();
+    let b: Group<(char, i32, bool, Char)> = scanner.next();
+    assert_eq!((5, 6, 7), a.into_inner());
+    assert_eq!(('a', 1, true, Char('x')), *b);
+
+    assert_eq!(None, Group::<[u8; 2]>::try_fparse("1,2,3"));
+    assert_eq!(None, Group::<(u8, u8)>::try_fparse("1,x"));
+    assert_eq!(Some(Group([1u8])), Group::<[u8; 1]>::try_fparse("1"));
+}
+
+#[test]
+#[should_panic(expected = "expected 3 elements separated by ',' in token \"1,2\", found 2")]
+fn read_group_too_short() {
+    let input = FastInput::with_reader("1,2".as_bytes());
+    input.next_token::<Group<[u8; 3]>>();
+}
+
+#[test]
+fn read_trimmed_line() {
+    let input = FastInput::with_reader(" a b \r\n\t\n".as_bytes());
+    assert_eq!("a b", input.next_line_trimmed());
+    assert_eq!("", input.next_line_trimmed());
+    assert!(!input.has_next_line());
+}
+
+#[test]
+fn read_from_path() {
+    let path = std::env::temp_dir().join(format!("fast_input_from_path_{}", std::process::id()));
+    std::fs::write(&path, "1 2\n3 4\n").unwrap();
+    let input = FastInput::from_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let pairs: Vec<(u8, u8)> = input.tuples().collect();
+    assert_eq!(vec![(1, 2), (3, 4)], pairs);
+    assert!(FastInput::from_path(&path).is_err());
+}
+
+#[test]
+fn build_input() {
+    let input = FastInputBuilder::default().build_from_reader(" 1  2 \r\n".as_bytes());
+    assert_eq!(vec!["1", "", "2"], input.next_split().collect::<Vec<_>>());
+
+    let input = FastInput::builder()
+        .delimiter(';')
+        .trim(false)
+        .crlf(false)
+        .null_token("-")
+        .capacity(1)
+        .build_from_reader("1;-;\r\nx\r\n".as_bytes());
+    let values: Vec<Option<Str>> = input.next_as_iter_opt().collect();
+    assert_eq!(3, values.len());
+    assert!(values[0].as_ref().unwrap() == "1");
+    assert!(values[1].is_none());
+    assert!(values[2].as_ref().unwrap() == "\r");
+    let view = input.fork();
+    assert_eq!("x\r", view.next_line());
+    assert_eq!("x\r", input.clone().next_line());
+}
+
+#[test]
+fn collect_line() {
+    let input = FastInput::with_reader("2 1 2\na b\n".as_bytes());
+    let set: HashSet<u8> = input.next_collect();
+    assert_eq!(2, set.len());
+    let words: String = input.next_collect::<Str, Vec<_>>().iter().map(|w| **w).collect();
+    assert_eq!("ab", words);
+}
+
+#[test]
+#[should_panic(expected = "failed to parse \"300\" as u8: ParseIntError { kind: PosOverflow }")]
+fn overflow_message() {
+    let input = FastInput::with_reader("1 300".as_bytes());
+    let _: (u8, u8) = input.next();
+}
+
+#[test]
+fn read_tuple_with_separators() {
+    let input = FastInput::with_reader(" 1-2:3 \na=b=c\n".as_bytes());
+    let (a, b, c): (u8, u8, u8) = input.next_tuple_seps(&['-', ':']);
+    assert_eq!((1, 2, 3), (a, b, c));
+    let (key, value): (String, String) = input.next_tuple_seps(&['=']);
+    assert_eq!(("a".to_owned(), "b=c".to_owned()), (key, value));
+}
+
+#[test]
+#[should_panic(expected = "separator ':' not found in line \"1-2\"")]
+fn read_tuple_with_missing_separator() {
+    let input = FastInput::with_reader("1-2\n".as_bytes());
+    let _: (u8, u8, u8) = input.next_tuple_seps(&['-', ':']);
+}
+
+#[test]
+#[should_panic(expected = "expected 2 separators for 3 elements, got 1")]
+fn read_tuple_with_too_few_separators() {
+    let input = FastInput::with_reader("1-2-3\n".as_bytes());
+    let _: (u8, u8, u8) = input.next_tuple_seps(&['-']);
+}
+
+#[test]
+fn raw_bytes() {
+    let input = streaming(vec!["ab\n", "cd"]);
+    input.next_line();
+    assert_eq!(b"cd", input.remaining_bytes());
+    assert_eq!(b"ab\ncd", input.as_bytes());
+    assert_eq!(3, input.position());
+}
+
+#[test]
+fn read_matrix() {
+    let input = FastInput::with_reader("2 2\n1 2\n3 4\n0 5\n".as_bytes());
+    assert_eq!(vec![vec![1, 2], vec![3, 4]], input.next_matrix::<i8>());
+    assert_eq!(Vec::<Vec<i8>>::new(), input.next_matrix::<i8>());
+    assert!(!input.has_next_line());
+}
+
+#[test]
+#[should_panic(expected = "row 1 has 3 elements, expected 2")]
+fn read_matrix_with_long_row() {
+    let input = FastInput::with_reader("2 2\n1 2\n3 4 5\n".as_bytes());
+    input.next_matrix::<i8>();
+}
+
+#[test]
+#[should_panic(expected = "expected 3 rows")]
+fn read_matrix_with_missing_rows() {
+    let input = FastInput::with_reader("3 1\n1\n2\n".as_bytes());
+    input.next_matrix::<i8>();
+}
+
+#[test]
+fn read_chars() {
+    let input = FastInput::with_reader(" åb \n\n".as_bytes());
+    assert_eq!(vec![' ', 'å', 'b', ' '], input.next_chars());
+    assert!(input.next_chars().is_empty());
+}
+
+#[test]
+fn iterate_tokens() {
+    let input = streaming(vec!["\n 1 2", "3\n\n", "-4 \n"]);
+    let all: Vec<i32> = input.tokens().collect();
+    assert_eq!(vec![1, 23, -4], all);
+    assert_eq!(0, FastInput::with_reader(" \n ".as_bytes()).tokens::<i32>().count());
+}
+
+#[test]
+fn read_flag_rows() {
+    let input = FastInput::with_reader("#.#\r\n..\n".as_bytes());
+    assert_eq!(vec![true, false, true], input.next_flag_row('#'));
+    assert_eq!(vec![true, true], input.next_flag_row('.'));
+}
+
+#[test]
+fn split_into_buffer() {
+    let input = FastInput::with_reader("a b c\nd\n".as_bytes());
+    let mut buf = vec!["old"];
+    input.next_split_into(&mut buf);
+    assert_eq!(vec!["a", "b", "c"], buf);
+    let capacity = buf.capacity();
+    input.next_split_into(&mut buf);
+    assert_eq!(vec!["d"], buf);
+    assert_eq!(capacity, buf.capacity());
+}
+
+#[test]
+fn read_until_byte() {
+    let input = streaming(vec!["1,2", "\n,", "3"]);
+    assert_eq!("1", input.next_until(b','));
+    assert_eq!("2\n", input.next_until(b','));
+    assert_eq!("3", input.next_until(b','));
+    assert!(!input.has_next_line());
+    assert_eq!("", input.next_until(b','));
+}
+
+#[test]
+#[should_panic(expected = "is not ASCII")]
+fn read_until_non_ascii() {
+    let input = FastInput::with_reader("å".as_bytes());
+    input.next_until(0xa5);
+}
+
+#[test]
+fn nonempty_lines_remain() {
+    let input = FastInput::with_reader("a\n \t\r\n".as_bytes());
+    assert!(input.has_next_nonempty_line());
+    input.next_line();
+    assert!(input.has_next_line());
+    assert!(!input.has_next_nonempty_line());
+    assert!(!FastInput::with_reader("".as_bytes()).has_next_nonempty_line());
+}
+
+#[test]
+fn scanner_default_on_missing_token() {
+    let input = FastInput::with_reader("3\n".as_bytes());
+    let mut scanner = input.line_scanner();
+    assert_eq!(3, scanner.next_or(0));
+    assert_eq!(7, scanner.next_or(7));
+}
+
+#[test]
+#[should_panic(expected = "failed to parse \"x\" as i32")]
+fn scanner_default_on_invalid_token() {
+    let input = FastInput::with_reader("x\n".as_bytes());
+    input.line_scanner().next_or(0);
+}
+
+#[test]
+fn drain_streaming_lines() {
+    let text: String = (0..10000).map(|i| format!("line {}\n", i)).collect();
+    let mut input = FastInput::streaming_from(std::io::Cursor::new(text));
+    let mut count = 0;
+    for (i, line) in input.drain_lines().enumerate() {
+        assert_eq!(format!("line {}", i), line);
+        count += 1;
+    }
+    assert_eq!(10000, count);
+    assert!(input.data().len() < 4 * BUFFER_SIZE);
+    assert!(input.retired.borrow().is_empty());
+}
+
+#[test]
+fn drain_lines_without_streaming() {
+    let mut input = FastInput::with_reader("a\nb".as_bytes());
+    input.next_line();
+    assert_eq!(vec!["b"], input.drain_lines().collect::<Vec<_>>());
+}
+
+#[test]
+fn classify_tokens() {
+    let input = FastInput::with_reader("0 +7 -.5 1e9 99999999999999999999 - inf x1 1x".as_bytes());
+    let kinds: Vec<TokenKind> = (0..9)
+        .map(|_| {
+            let kind = input.peek_token_kind();
+            input.next_word();
+            kind
+        })
+        .collect();
+    use TokenKind::*;
+    assert_eq!(vec![Integer, Integer, Float, Float, Integer, Word, Word, Word, Word], kinds);
+    assert_eq!(Empty, input.peek_token_kind());
+}
+
+#[test]
+fn keep_empty_fields() {
+    let input = FastInput::with_reader("a,,c,\n1,,3\n\tx\t\t\n\tx\t\t\n".as_bytes()).with_delimiter(',');
+    assert_eq!(vec!["a", "", "c", ""], input.next_split().collect::<Vec<_>>());
+    let values: Vec<Option<u8>> = input.next_as_iter_opt().collect();
+    assert_eq!(vec![Some(1), None, Some(3)], values);
+    let input = input.with_delimiter('\t');
+    assert_eq!(vec!["x"], input.next_split().collect::<Vec<_>>());
+    let input = input.with_trim(false);
+    assert_eq!(vec!["", "x", "", ""], input.next_split().collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic(expected = "failed to parse \"\" as u8")]
+fn empty_required_field() {
+    let input = FastInput::with_reader("1,,3".as_bytes()).with_delimiter(',');
+    input.next_as_iter::<u8>().count();
+}
+
+#[test]
+fn fill_buf_and_consume() {
+    let input = streaming(vec!["ab", "c"]);
+    assert_eq!(b"ab", input.fill_buf());
+    input.consume(1);
+    assert_eq!(b"b", input.fill_buf());
+    input.consume(1);
+    assert_eq!(b"c", input.fill_buf());
+    input.consume(1);
+    assert!(input.fill_buf().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "can not consume 3 bytes")]
+fn consume_too_much() {
+    let input = FastInput::with_reader("ab".as_bytes());
+    input.consume(3);
+}
+
+#[test]
+#[should_panic(expected = "past the buffered data")]
+fn consume_overflowing() {
+    let input = FastInput::with_reader("ab".as_bytes());
+    input.consume(1);
+    input.consume(usize::MAX);
+}
+
+#[test]
+#[should_panic(expected = "position 1 is not a character boundary")]
+fn consume_inside_char() {
+    let input = FastInput::with_reader_checked("\u{e9}".as_bytes()).unwrap();
+    input.consume(1);
+}
+
+#[test]
+fn read_ratios() {
+    let input = FastInput::with_reader("1/0 -3/-4 5".as_bytes());
+    let (a, b): (Ratio, Ratio) = input.next();
+    assert_eq!((1, 0), *a);
+    assert_eq!((-3, -4), *b);
+    assert_eq!("-3/-4", b.to_string());
+    assert_eq!(None, Ratio::try_fparse("5"));
+    assert_eq!(None, Ratio::try_fparse("1/x"));
+}
+
+#[test]
+#[should_panic(expected = "missing '/' in ratio \"5\"")]
+fn read_ratio_without_slash() {
+    let input = FastInput::with_reader("5".as_bytes());
+    input.next_token::<Ratio>();
+}
+
+#[test]
+fn read_padded_tuple() {
+    let input = FastInput::with_reader("1 x\n-2\n".as_bytes());
+    assert_eq!((Some(1), Some('x')), input.next_tuple_opt::<i8, char>());
+    assert_eq!((Some(-2), None), input.next_tuple_opt::<i8, char>());
+    assert_eq!((None, None), input.next_tuple_opt::<i8, char>());
+}
+
+#[test]
+#[should_panic(expected = "failed to parse \"x\" as u8")]
+fn read_padded_tuple_invalid() {
+    let input = FastInput::with_reader("1 x".as_bytes());
+    input.next_tuple_opt::<u8, u8>();
+}
+
+#[test]
+fn longest_line() {
+    let input = streaming(vec!["a\n", "abc\r", "\nab"]);
+    input.next_line();
+    assert_eq!(3, input.max_line_len());
+    assert_eq!(3, input.max_line_len());
+    assert_eq!(2, input.position());
+    assert_eq!(0, FastInput::with_reader("".as_bytes()).max_line_len());
+    let input = FastInput::builder().crlf(false).build_from_reader("ab\r\n".as_bytes());
+    assert_eq!(3, input.max_line_len());
+}
+
+#[test]
+fn read_grouped_numbers() {
+    let input = FastInput::with_reader("1,234 -5,000.5 12 1.000".as_bytes());
+    let (a, b, c, d): (Grouped<u32>, Grouped<f64>, Grouped<u8>, Grouped<u32, '.'>) = input.next();
+    assert_eq!((1234, -5000.5, 12, 1000), (*a, *b, c.into_inner(), *d));
+    assert_eq!(None, Grouped::<u8>::try_fparse("1,000"));
+    assert_eq!(Some(Grouped(100u8)), Grouped::<u8>::try_fparse("1,00"));
+}
+
+#[test]
+#[should_panic(expected = "failed to parse \"1x\" as u32")]
+fn read_grouped_invalid() {
+    let input = FastInput::with_reader("1,x".as_bytes());
+    input.next_token::<Grouped<u32>>();
+}
+
+#[test]
+fn unread_lines() {
+    let input = FastInput::with_reader("1 2\r\n3 4\n".as_bytes());
+    let first: (u8, u8) = input.next();
+    input.unread_line();
+    assert_eq!("1 2", input.next_line());
+    assert_eq!(Some("3 4"), input.try_next_line());
+    input.unread_line();
+    assert_eq!(vec![3, 4], input.next_as_iter::<u8>().collect::<Vec<_>>());
+    assert_eq!((1, 2), first);
+}
+
+#[test]
+#[should_panic(expected = "no line to unread")]
+fn unread_line_twice() {
+    let input = FastInput::with_reader("1\n2\n".as_bytes());
+    input.next_line();
+    input.next_line();
+    input.unread_line();
+    input.unread_line();
+}
+
+#[test]
+fn unread_line_after_moving() {
+    let input = FastInput::with_reader("1\n2\n3\n".as_bytes());
+    let can_unread = || std::panic::catch_unwind(AssertUnwindSafe(|| input.unread_line())).is_ok();
+    input.next_line();
+    input.next_line();
+    input.reset();
+    assert!(!can_unread());
+
+    let checkpoint = input.checkpoint();
+    input.next_line();
+    input.restore(checkpoint);
+    assert!(!can_unread());
+
+    input.next_line();
+    input.seek(4);
+    assert!(!can_unread());
+
+    input.seek(2);
+    input.next_line();
+    assert_eq!(3u8, input.next_token());
+    assert!(!can_unread());
+}
+
+#[test]
+fn read_all_pairs() {
+    let input = FastInput::with_reader("a 1\nb\n\n2 c".as_bytes());
+    let pairs: Vec<(char, u8)> = input.all_pairs().collect();
+    assert_eq!(vec![('a', 1), ('b', 2)], pairs);
+    assert!(!input.has_next_token());
+}
+
+#[test]
+fn read_chars_one_at_a_time() {
+    let smiley = "\u{1F600}".as_bytes();
+    let chunks = vec![b"a\xc3\xa5".to_vec(), smiley[..2].to_vec(), smiley[2..].to_vec()];
+    let input = FastInput::from_source(Box::new(BufReader::new(Chunked(chunks.into()))));
+    assert_eq!(Some('a'), input.next_char());
+    assert_eq!(Some('å'), input.next_char());
+    assert_eq!(3, input.position());
+    assert_eq!(Some('\u{1F600}'), input.next_char());
+    assert_eq!(None, input.next_char());
+}
+
+#[test]
+#[should_panic(expected = "invalid UTF-8 at byte 1")]
+fn read_truncated_char() {
+    let input = FastInput::from_bytes(b"a\xc3".to_vec());
+    input.next_char();
+    input.next_char();
+}
+
+#[test]
+fn read_kv_lines() {
+    let input = FastInput::with_reader("a b\n1\nc\n2\n".as_bytes());
+    let (key, value): (String, u8) = input.next_kv_lines();
+    assert_eq!(("a b", 1), (key.as_str(), value));
+    let rest: Vec<(String, u8)> = input.kv_lines().collect();
+    assert_eq!(vec![("c".to_string(), 2)], rest);
+}
+
+#[test]
+#[should_panic(expected = "missing value line")]
+fn read_kv_lines_odd() {
+    let input = FastInput::with_reader("a\n1\nb\n".as_bytes());
+    let _: Vec<(String, u8)> = input.kv_lines().collect();
+}
+
+#[test]
+fn read_concatenated_readers() {
+    let readers: Vec<Box<dyn Read>> = vec![
+        Box::new("\u{feff}1 2".as_bytes()),
+        Box::new("".as_bytes()),
+        Box::new("\u{feff}3 4\n".as_bytes()),
+        Box::new("5".as_bytes()),
+    ];
+    let input = FastInput::from_readers(readers);
+    assert_eq!(b"1 2\n3 4\n5", input.as_bytes());
+}
+
+#[test]
+fn compare_str_ignoring_case() {
+    let input = FastInput::with_reader("Quit ÅÄÖ".as_bytes());
+    let (command, name): (Str, Str) = input.next();
+    assert!(command.eq_ignore_case("QUIT"));
+    assert!(!command.eq_ignore_case("quiet"));
+    assert_eq!("quit", command.to_lowercase_owned());
+    assert_eq!("åäö", name.to_lowercase_owned());
+}
+
+#[test]
+fn report_progress_in_bytes() {
+    let input = FastInput::with_reader("\u{feff}ab\ncd".as_bytes());
+    assert_eq!((0, 5), (input.bytes_consumed(), input.bytes_total()));
+    input.next_line();
+    assert_eq!((3, 5), (input.bytes_consumed(), input.bytes_total()));
+
+    let input = streaming(vec!["ab\n", "cd"]);
+    input.next_line();
+    assert_eq!((3, 3), (input.bytes_consumed(), input.bytes_total()));
+    input.next_line();
+    assert_eq!((5, 5), (input.bytes_consumed(), input.bytes_total()));
+}
+
+#[test]
+fn lines_with_and_without_trailing_newline() {
+    for &data in &["abc", "abc\n", "abc\r\n"] {
+        let input = FastInput::with_reader(data.as_bytes());
+        assert_eq!(vec!["abc"], input.lines().collect::<Vec<_>>(), "{:?}", data);
+        assert!(!input.has_next_line());
+
+        let input = FastInput::with_reader(data.as_bytes());
+        assert_eq!(1, input.lines().count(), "{:?}", data);
+        let input = FastInput::with_reader(data.as_bytes());
+        assert_eq!(vec!["abc"], input.fork().lines().collect::<Vec<_>>(), "{:?}", data);
+
+        let input = streaming(vec![data]);
+        assert_eq!(vec!["abc"], input.lines().collect::<Vec<_>>(), "{:?}", data);
+    }
+    for &data in &["a\nbc", "a\nbc\n"] {
+        let input = FastInput::with_reader(data.as_bytes());
+        assert_eq!(2, input.remaining_lines(), "{:?}", data);
+        assert_eq!(vec!["a", "bc"], input.lines().collect::<Vec<_>>(), "{:?}", data);
+    }
+}
+
+#[test]
+fn read_line_of_pairs() {
+    let input = FastInput::with_reader("a 1 b 2\n1 2 3".as_bytes());
+    let pairs: Vec<(char, u8)> = input.next_tuple_vec();
+    assert_eq!(vec![('a', 1), ('b', 2)], pairs);
+
+    let input = FastInput::with_reader("\n1,,2,\n".as_bytes()).with_delimiter(',');
+    assert!(input.next_tuple_vec::<u8, u8>().is_empty());
+    assert_eq!(vec![(1, 2)], input.next_tuple_vec::<u8, u8>());
+}
+
+#[test]
+#[should_panic(expected = "odd number of elements (3)")]
+fn read_line_of_pairs_odd() {
+    let input = FastInput::with_reader("1 2 3".as_bytes());
+    let _: Vec<(u8, u8)> = input.next_tuple_vec();
+}
+
+#[test]
+fn parse_radix_generically() {
+    fn parse_all<T: FromStrRadix>(tokens: &[&str], radix: u32) -> Vec<T> {
+        tokens.iter().map(|t| T::fparse_radix(t, radix)).collect()
+    }
+    assert_eq!(vec![10u16, 255, 1], parse_all(&["a", "0XFF", "1"], 16));
+    assert_eq!(None, u8::try_fparse_radix("0x100", 16));
+    assert_eq!(None, u8::try_fparse_radix("0x", 16));
+}
+
+#[test]
+fn read_with_line_terminator() {
+    let input = FastInput::with_reader("a b;c\nd;;e;".as_bytes()).with_line_terminator(b';');
+    assert_eq!(4, input.total_lines());
+    assert_eq!(3, input.max_line_len());
+    assert_eq!(4, input.remaining_lines());
+    let mut scanner = input.line_scanner();
+    assert_eq!(Some('a'), scanner.try_next());
+    assert_eq!(Some('b'), scanner.try_next());
+    assert_eq!(None, scanner.try_next::<char>());
+    input.finish_line();
+    assert_eq!(2, input.current_line_number());
+    assert_eq!(vec!["c\nd", "", "e"], input.fork().lines().collect::<Vec<_>>());
+    assert_eq!(vec!["c\nd", "", "e"], input.lines().collect::<Vec<_>>());
+
+    let input = FastInput::builder()
+        .line_terminator(b',')
+        .build_from_reader("1,2\n".as_bytes());
+    assert_eq!(vec!["1", "2\n"], input.lines().collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic(expected = "line terminator 169 is not ASCII")]
+fn non_ascii_line_terminator() {
+    FastInput::from_str("\u{e9}").with_line_terminator(0xa9);
+}
+
+#[test]
+#[should_panic(expected = "line terminator 169 is not ASCII")]
+fn non_ascii_line_terminator_in_builder() {
+    FastInput::builder().line_terminator(0xa9);
+}
+
+#[test]
+fn nonempty_lines_with_line_terminator() {
+    let input = FastInput::from_str("1;;").with_line_terminator(b';');
+    assert!(input.has_next_nonempty_line());
+    input.next_line();
+    assert!(!input.has_next_nonempty_line());
+
+    let input = FastInput::from_str("1; \n;x").with_line_terminator(b';');
+    input.next_line();
+    assert!(input.has_next_nonempty_line());
+    assert_eq!(" \n", input.next_line());
+}
+
+#[test]
+fn read_blank_line_as_optional_pair() {
+    let input = FastInput::with_reader("\n  \n,2".as_bytes()).with_delimiter(',');
+    assert_eq!((None, None), input.next_tuple_opt::<u8, u8>());
+    assert_eq!((None, None), input.next_tuple_opt::<u8, u8>());
+    assert_eq!((None, Some(2)), input.next_tuple_opt::<u8, u8>());
+}
+
+#[test]
+#[should_panic(expected = "expected 3 rows")]
+fn read_grid_with_missing_rows() {
+    let input = FastInput::with_reader("a b\n".as_bytes());
+    input.next_grid::<Str>(3);
+}
+
+#[test]
+fn read_grouped_with_configured_separator() {
+    let input = FastInput::builder()
+        .group_separator('\'')
+        .build_from_reader("1'000 -3'000'000 x".as_bytes());
+    assert_eq!(1000u32, input.next_grouped());
+    assert_eq!(-3_000_000i64, input.next_grouped());
+
+    let input = FastInput::with_reader("12,345.5".as_bytes());
+    assert_eq!(12345.5f64, input.next_grouped());
+}
+
+#[test]
+#[should_panic(expected = "failed to parse \"x\"")]
+fn read_invalid_grouped() {
+    let input = FastInput::with_reader("x".as_bytes());
+    input.next_grouped::<u32>();
+}
+
+#[test]
+#[allow(clippy::manual_is_multiple_of)]
+fn custom_try_fparse() {
+    #[derive(Debug, PartialEq)]
+    struct Even(u32);
+
+    impl<'a> TryFParse<'a> for Even {
+        fn try_fparse(s: &'a str) -> Option<Self> {
+            s.parse().ok().filter(|n| n % 2 == 0).map(Even)
+        }
+    }
+
+    assert_eq!(Some(Even(4)), Even::try_fparse("4"));
+    assert_eq!(None, Even::try_fparse("3"));
+    assert_eq!(None, Even::try_fparse("x"));
+    let input = FastInput::with_reader("4 3".as_bytes());
+    let read: Result<(Even, Even), _> = input.try_next();
+    assert_eq!(Err(ParseError::InvalidToken {
+        token: "3".to_owned(),
+        expected: type_name::<Even>(),
+        position: 2,
+    }), read);
+}